@@ -0,0 +1,55 @@
+//! Times render_frame for the cube at a few angles, at the default screen size and a larger one, as a wireframe and filled, and with and without the depth buffer, and how much reusing one frame buffer saves
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use simple_rust_cube::{look_at, render_frame, render_scene_into, rotation_x, rotation_y, Frame, Mesh, Model, RenderMode, RenderOptions, Vector, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+/// Angles the cube is turned through, in radians
+const ANGLES : [f32; 3] = [0.0, 0.5, 1.0];
+
+/// Screen sizes the cube is drawn at, in characters
+const SIZES : [(usize, usize); 2] = [(SCREEN_WIDTH, SCREEN_HEIGHT), (200, 100)];
+
+fn bench_render_frame(c: &mut Criterion) {
+    let mesh = Mesh::cube();
+    let view = look_at(&Vector([0.0, 0.0, 2.5, 1.0]), &Vector([0.0, 0.0, 0.0, 1.0]), &Vector([0.0, 1.0, 0.0, 0.0]));
+    let variants = [
+        ("wireframe", RenderOptions { view, ..RenderOptions::default() }),
+        ("wireframe without depth test", RenderOptions { view, depth_test: false, ..RenderOptions::default() }),
+        ("filled", RenderOptions { view, mode: RenderMode::Filled, ..RenderOptions::default() }),
+    ];
+    for (name, options) in &variants {
+        let mut group = c.benchmark_group(format!("render_frame {}", name));
+        for (width, height) in SIZES {
+            for angle in ANGLES {
+                let model_to_world = rotation_x(0.5 * angle) * rotation_y(angle);
+                group.bench_with_input(BenchmarkId::new(format!("{}x{}", width, height), angle), &model_to_world, |b, model_to_world| {
+                    b.iter(|| render_frame(&mesh, model_to_world, options, width, height))
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+/// Compares drawing each frame into a new frame buffer with clearing and drawing into the same one
+fn bench_reused_frame(c: &mut Criterion) {
+    let mesh = Mesh::cube();
+    let view = look_at(&Vector([0.0, 0.0, 2.5, 1.0]), &Vector([0.0, 0.0, 0.0, 1.0]), &Vector([0.0, 1.0, 0.0, 0.0]));
+    let options = RenderOptions { view, ..RenderOptions::default() };
+    let model_to_world = rotation_x(0.25) * rotation_y(0.5);
+    let mut group = c.benchmark_group("frame buffer");
+    for (width, height) in SIZES {
+        let size = format!("{}x{}", width, height);
+        group.bench_function(BenchmarkId::new("new", &size), |b| {
+            b.iter(|| render_frame(&mesh, &model_to_world, &options, width, height))
+        });
+        let mut frame = Frame::new(width, height);
+        group.bench_function(BenchmarkId::new("reused", &size), |b| {
+            b.iter(|| render_scene_into(&mut frame, &[Model { mesh: &mesh, model_to_world }], &options))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_render_frame, bench_reused_frame);
+criterion_main!(benches);
@@ -0,0 +1,150 @@
+//! Steering the cube with the keyboard and the mouse
+
+use crate::input::{Event, Key, MouseEvent, MouseKind};
+use crate::quat::Quat;
+use crate::Vector;
+
+/// How far one press of an arrow key turns the cube, in radians
+pub const KEY_STEP : f32 = 0.1;
+
+/// Returns how far a key turns the cube, as the change in yaw around the y-axis and pitch around the x-axis. The arrow keys move the front of the cube in their direction.
+pub fn key_rotation(key: Key) -> Option<(f32, f32)> {
+    match key {
+        Key::Left => Some((KEY_STEP, 0.0)),
+        Key::Right => Some((-KEY_STEP, 0.0)),
+        Key::Up => Some((0.0, -KEY_STEP)),
+        Key::Down => Some((0.0, KEY_STEP)),
+        Key::Char(_) => None,
+    }
+}
+
+/// How far dragging the mouse by one column turns the cube, in radians. Rows are about twice as tall as columns are wide, so dragging by one row turns it twice as far.
+pub const DRAG_STEP : f32 = 0.05;
+
+/// Narrowest field of view, in degrees
+pub const MIN_FOV : f32 = 10.0;
+
+/// Widest field of view, in degrees
+pub const MAX_FOV : f32 = 120.0;
+
+/// How much one press of + or - changes the field of view, in degrees
+pub const FOV_STEP : f32 = 5.0;
+
+/// Nearest the camera can come to the cube. The corners of the cube are the square root of 3 from its center, so this keeps the camera outside it, with its nearest corner well beyond the near plane.
+pub const MIN_DISTANCE : f32 = 2.0;
+
+/// Farthest the camera can go from the cube
+pub const MAX_DISTANCE : f32 = 50.0;
+
+/// How much one press of f or b moves the camera, as a fraction of its distance, so that it moves as fast on the screen near and far
+pub const DISTANCE_STEP : f32 = 0.1;
+
+/// The orientation of the cube, as steered by the keyboard and mouse
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Controls {
+    /// How the cube is turned, kept as a quaternion so that turning it many times in different directions neither locks up nor builds up skew
+    pub orientation: Quat,
+    /// Whether the cube also spins around the y-axis on its own
+    pub auto_spin: bool,
+    /// Whether the animation is paused, so that the cube does not spin on its own
+    pub paused: bool,
+    /// Whether the user has asked to quit
+    pub quit: bool,
+    /// Whether the user has asked for a screenshot of the next frame
+    pub screenshot: bool,
+    /// Where the mouse was last seen while dragging, or None when no button is held down
+    pub drag: Option<(u16, u16)>,
+    /// Vertical field of view in degrees
+    pub fov: f32,
+    /// How far the camera is from the cube
+    pub distance: f32,
+}
+
+impl Controls {
+    /// The controls as they are at the start, with the given field of view in degrees and distance of the camera from the cube
+    pub fn new(fov: f32, distance: f32) -> Self {
+        Controls {
+            orientation: Quat::IDENTITY,
+            auto_spin: true,
+            paused: false,
+            quit: false,
+            screenshot: false,
+            drag: None,
+            fov,
+            distance,
+        }
+    }
+
+    /// Turns the cube by yaw radians like rotation_y around its own y-axis, and then by pitch radians like rotation_x around the x-axis of the screen
+    pub fn turn(&mut self, yaw: f32, pitch: f32) {
+        // rotation_axis turns the other way from rotation_x, so the pitch is negated.
+        let yaw = Quat::from_axis_angle(&Vector([0.0, 1.0, 0.0, 0.0]), yaw);
+        let pitch = Quat::from_axis_angle(&Vector([1.0, 0.0, 0.0, 0.0]), -pitch);
+        self.orientation = (pitch * self.orientation * yaw).normalize();
+    }
+
+    /// Turns the cube by angle radians around one of its own axes, the same way as rotation_axis
+    pub fn spin(&mut self, axis: &Vector, angle: f32) {
+        self.turn_by(Quat::from_axis_angle(axis, angle));
+    }
+
+    /// Turns the cube by a rotation in its own coordinates
+    pub fn turn_by(&mut self, rotation: Quat) {
+        self.orientation = (self.orientation * rotation).normalize();
+    }
+
+    /// Zooms in by narrowing the field of view so that things look factor times as big, or out for a factor less than 1, within the limits of the field of view
+    pub fn zoom(&mut self, factor: f32) {
+        let half_height = (self.fov.to_radians() / 2.0).tan() / factor;
+        self.fov = (2.0 * half_height.atan()).to_degrees().clamp(MIN_FOV, MAX_FOV);
+    }
+
+    /// Updates the controls for a key press. The arrow keys turn the cube, 'a' switches spinning on its own on and off, '+' and '-' zoom in and out, 'f' and 'b' move the camera forwards and back, Space pauses and resumes the animation, 's' takes a screenshot and 'q' quits.
+    pub fn handle_key(&mut self, key: Key) {
+        if let Some((yaw, pitch)) = key_rotation(key) {
+            self.turn(yaw, pitch);
+            return;
+        }
+        match key {
+            Key::Char('a') => self.auto_spin = !self.auto_spin,
+            // '=' is on the same key as '+' on most keyboards, so it zooms in without Shift.
+            Key::Char('+' | '=') => self.fov = (self.fov - FOV_STEP).max(MIN_FOV),
+            Key::Char('-') => self.fov = (self.fov + FOV_STEP).min(MAX_FOV),
+            Key::Char('f') => self.distance = (self.distance * (1.0 - DISTANCE_STEP)).max(MIN_DISTANCE),
+            Key::Char('b') => self.distance = (self.distance * (1.0 + DISTANCE_STEP)).min(MAX_DISTANCE),
+            Key::Char(' ') => self.paused = !self.paused,
+            Key::Char('s') => self.screenshot = true,
+            Key::Char('q') => self.quit = true,
+            _ => {}
+        }
+    }
+
+    /// Updates the controls for a mouse event. Dragging turns the cube so that its front follows the mouse.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let position = (mouse.column, mouse.row);
+        match mouse.kind {
+            MouseKind::Press => self.drag = Some(position),
+            MouseKind::Drag => {
+                if let Some((column, row)) = self.drag {
+                    self.turn((column as f32 - mouse.column as f32) * DRAG_STEP, (mouse.row as f32 - row as f32) * DRAG_STEP * 2.0);
+                }
+                self.drag = Some(position);
+            }
+            MouseKind::Release => self.drag = None,
+            MouseKind::Move => {}
+        }
+    }
+
+    /// Updates the controls for anything the user did
+    pub fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            Event::Mouse(mouse) => self.handle_mouse(mouse),
+        }
+    }
+
+    /// Whether the cube is currently spinning on its own
+    pub fn spinning(&self) -> bool {
+        self.auto_spin && !self.paused
+    }
+}
@@ -0,0 +1,170 @@
+//! Saving frames as images
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use gif::{EncodingError, Encoder, Repeat};
+
+use crate::{frame_to_string, project_scene, project_segment, unique_edges, Frame, Model, RenderOptions};
+
+/// The 16 basic terminal colors, in the shades xterm uses for them
+const BASIC_COLORS : [[u8; 3]; 16] = [
+    [0, 0, 0], [205, 0, 0], [0, 205, 0], [205, 205, 0], [0, 0, 238], [205, 0, 205], [0, 205, 205], [229, 229, 229],
+    [127, 127, 127], [255, 0, 0], [0, 255, 0], [255, 255, 0], [92, 92, 255], [255, 0, 255], [0, 255, 255], [255, 255, 255],
+];
+
+/// Returns the red, green and blue components of a 256-color terminal color. After the 16 basic colors come a 6x6x6 cube of colors and then 24 shades of gray.
+pub fn color_to_rgb(color: u8) -> [u8; 3] {
+    match color {
+        0..=15 => BASIC_COLORS[color as usize],
+        16..=231 => {
+            let level = |i: u8| if i == 0 { 0 } else { 55 + 40 * i };
+            let i = color - 16;
+            [level(i / 36), level(i / 6 % 6), level(i % 6)]
+        }
+        232..=255 => [8 + 10 * (color - 232); 3],
+    }
+}
+
+/// Returns the color of the 6x6x6 cube of 256-color terminal colors that is nearest to a 24-bit color, going by each component on its own
+pub fn nearest_color([r, g, b]: [u8; 3]) -> u8 {
+    // The levels of the cube are 0 and then 95 to 255 in steps of 40, as color_to_rgb gives them.
+    let level = |c: u8| if c < 48 { 0 } else { ((c as i32 - 55 + 20) / 40).clamp(1, 5) as u8 };
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+/// Terminal color of the pixels of a cell in an image of a frame: the color of whatever has been drawn into it, white if that has no color, or black if the cell is empty or holds a space
+fn cell_color(glyph: char, color: Option<u8>, background: char) -> u8 {
+    match color {
+        _ if glyph == ' ' || glyph == background => 0,
+        Some(color) => color,
+        None => 15,
+    }
+}
+
+/// Encodes a frame as a binary PPM image, in which each cell is a block of cell_width by cell_height pixels in the color given by cell_color, or in its 24-bit color if it has one
+pub fn frame_to_ppm(frame: &Frame, [cell_width, cell_height]: [usize; 2]) -> Vec<u8> {
+    let (width, height) = (frame.width() * cell_width, frame.height() * cell_height);
+    let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    ppm.reserve(width * height * 3);
+    for y in 0..frame.height() {
+        let mut row = Vec::with_capacity(width * 3);
+        for ((&glyph, &color), &rgb) in frame.row(y).iter().zip(frame.row_colors(y)).zip(frame.row_rgb(y)) {
+            let rgb = match rgb {
+                Some(rgb) if glyph != ' ' && glyph != frame.background() => rgb,
+                _ => color_to_rgb(cell_color(glyph, color, frame.background())),
+            };
+            for _ in 0..cell_width {
+                row.extend_from_slice(&rgb);
+            }
+        }
+        for _ in 0..cell_height {
+            ppm.extend_from_slice(&row);
+        }
+    }
+    ppm
+}
+
+/// Writes frames one after another into an animated GIF that loops forever. The palette is the 256 terminal colors, so that a cell's color is its own palette index.
+pub struct GifExport<W: Write> {
+    encoder: Encoder<W>,
+    cell_size: [usize; 2],
+    /// Time each frame is shown for, in hundredths of a second
+    delay: u16,
+}
+
+impl<W: Write> GifExport<W> {
+    /// Starts a GIF of frames of width by height cells, each cell a block of cell_width by cell_height pixels, shown for delay each
+    pub fn new(out: W, [width, height]: [usize; 2], cell_size: [usize; 2], delay: Duration) -> Result<GifExport<W>, EncodingError> {
+        let dimension = |cells: usize, cell: usize| u16::try_from(cells * cell).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "image too large for a GIF"));
+        let palette: Vec<u8> = (0..=255).flat_map(color_to_rgb).collect();
+        let mut encoder = Encoder::new(out, dimension(width, cell_size[0])?, dimension(height, cell_size[1])?, &palette)?;
+        encoder.set_repeat(Repeat::Infinite)?;
+        let delay = (delay.as_millis() as f64 / 10.0).round().clamp(1.0, u16::MAX as f64) as u16;
+        Ok(GifExport { encoder, cell_size, delay })
+    }
+
+    /// Appends a frame to the animation
+    pub fn add_frame(&mut self, frame: &Frame) -> Result<(), EncodingError> {
+        let [cell_width, cell_height] = self.cell_size;
+        let (width, height) = (frame.width() * cell_width, frame.height() * cell_height);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..frame.height() {
+            let mut row = Vec::with_capacity(width);
+            for ((&glyph, &color), &rgb) in frame.row(y).iter().zip(frame.row_colors(y)).zip(frame.row_rgb(y)) {
+                // A cell in a 24-bit color is in the nearest of the palette's colors, since a GIF can only have 256.
+                let color = rgb.map(nearest_color).or(color);
+                row.extend(std::iter::repeat_n(cell_color(glyph, color, frame.background()), cell_width));
+            }
+            for _ in 0..cell_height {
+                pixels.extend_from_slice(&row);
+            }
+        }
+        let mut image = gif::Frame::from_indexed_pixels(width as u16, height as u16, pixels, None);
+        image.delay = self.delay;
+        self.encoder.write_frame(&image)
+    }
+
+    /// Finishes the GIF and returns the writer it was written to
+    pub fn finish(self) -> Result<W, EncodingError> {
+        self.encoder.into_inner()
+    }
+}
+
+/// Draws the wireframe of a scene as an SVG document the size of a screen of width by height cells, each cell_width by cell_height pixels. Instead of being drawn into a frame, the edges that render_scene would draw are written as lines between their projected ends, so they stay sharp however far the image is zoomed in. Lines are white, or in the color of their face as in render_scene, on black.
+pub fn frame_to_svg(models: &[Model], options: &RenderOptions, width: usize, height: usize, [cell_width, cell_height]: [usize; 2]) -> String {
+    let scene = project_scene(models, options, width, height);
+    let (svg_width, svg_height) = (width * cell_width, height * cell_height);
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n", svg_width, svg_height, svg_width, svg_height);
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"black\"/>\n");
+    // A position on the screen is in the middle of its cell.
+    let point = |[x, y, _]: [f32; 3]| [(x + 0.5) * cell_width as f32, (y + 0.5) * cell_height as f32];
+    for (model_index, model) in models.iter().enumerate() {
+        for edge in unique_edges(&model.mesh.faces) {
+            let Some(&face_index) = edge.faces.iter().find(|&&face_index| scene.visible[model_index][face_index]) else {
+                continue;
+            };
+            let [start, end] = edge.vertices.map(|i| scene.view_pos[model_index][i]);
+            let Some((start, end)) = project_segment(&scene.projection, &start, &end, options, width, height) else {
+                continue;
+            };
+            let [r, g, b] = match options.colors {
+                Some(colors) => color_to_rgb(colors[face_index % colors.len()]),
+                None => [255, 255, 255],
+            };
+            let ([x1, y1], [x2, y2]) = (point(start), point(end));
+            svg.push_str(&format!("<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#{:02x}{:02x}{:02x}\"/>\n", x1, y1, x2, y2, r, g, b));
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Name of the file a screenshot taken at time is saved in, such as screenshot-20240131-235959.123.txt, from the date and time in UTC to the millisecond
+pub fn screenshot_name(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (days, second) = (seconds / 86_400, seconds % 86_400);
+    // Turns the number of days since 1970-01-01 into a date in the proleptic Gregorian calendar, counting in eras of 400 years that start on the 1st of March, so that the leap day comes at the end of the year.
+    let days = days as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "screenshot-{:04}{:02}{:02}-{:02}{:02}{:02}.{:03}.txt",
+        year, month, day, second / 3600, second / 60 % 60, second % 60, since_epoch.subsec_millis()
+    )
+}
+
+/// Saves a frame as text, as frame_to_string writes it, in a file in dir named for the time by screenshot_name, and returns the path of the file
+pub fn save_screenshot(dir: &Path, frame: &Frame, time: SystemTime) -> io::Result<PathBuf> {
+    let path = dir.join(screenshot_name(time));
+    std::fs::write(&path, frame_to_string(frame))?;
+    Ok(path)
+}
@@ -0,0 +1,91 @@
+//! Parsing of the keyboard and mouse input read from the terminal
+
+/// A key pressed on the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// A key that types a character
+    Char(char),
+}
+
+/// What happened to the mouse in a mouse event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseKind {
+    /// A button was pressed
+    Press,
+    /// The mouse moved while a button was held down
+    Drag,
+    /// A button was released
+    Release,
+    /// The mouse moved with no button held down
+    Move,
+}
+
+/// A mouse event reported by the terminal, at a cell counted from 1 in the top left corner of the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseEvent {
+    pub kind: MouseKind,
+    pub column: u16,
+    pub row: u16,
+}
+
+/// Something the user did in the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Key(Key),
+    Mouse(MouseEvent),
+}
+
+/// Escape byte that starts the sequences sent for special keys
+const ESC : u8 = 0x1b;
+
+/// Parses the bytes read from a terminal into the events they encode. The arrow keys arrive as the escape sequences ESC [ A to ESC [ D, or ESC O A to ESC O D in application mode, and mouse events as SGR mouse sequences; other escape sequences and bytes that are not ASCII are skipped.
+pub fn parse_input(bytes: &[u8]) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == ESC && matches!(bytes.get(i + 1), Some(b'[' | b'O')) {
+            // The sequence ends with a byte in the range '@' to '~', after any parameters.
+            let Some(len) = bytes[i + 2..].iter().position(|b| (0x40..=0x7e).contains(b)) else {
+                break;
+            };
+            let end = i + 2 + len;
+            let event = match bytes[end] {
+                b'A' => Some(Event::Key(Key::Up)),
+                b'B' => Some(Event::Key(Key::Down)),
+                b'C' => Some(Event::Key(Key::Right)),
+                b'D' => Some(Event::Key(Key::Left)),
+                b'M' | b'm' => parse_sgr_mouse(&bytes[i + 2..end], bytes[end] == b'm').map(Event::Mouse),
+                _ => None,
+            };
+            events.extend(event);
+            i = end + 1;
+            continue;
+        }
+        if bytes[i].is_ascii() {
+            events.push(Event::Key(Key::Char(bytes[i] as char)));
+        }
+        i += 1;
+    }
+    events
+}
+
+/// Parses the parameters of an SGR mouse sequence, ESC [ < button ; column ; row followed by M for a press or motion or m for a release. The button code has 32 added while the mouse is moving, and 3 in its low bits when no button is held down.
+fn parse_sgr_mouse(params: &[u8], release: bool) -> Option<MouseEvent> {
+    let params = std::str::from_utf8(params.strip_prefix(b"<")?).ok()?;
+    let mut fields = params.split(';').map(|field| field.parse::<u16>().ok());
+    let (button, column, row) = (fields.next()??, fields.next()??, fields.next()??);
+    let kind = if release {
+        MouseKind::Release
+    } else if button & 32 == 0 {
+        MouseKind::Press
+    } else if button & 3 == 3 {
+        MouseKind::Move
+    } else {
+        MouseKind::Drag
+    };
+    Some(MouseEvent { kind, column, row })
+}
@@ -0,0 +1,2012 @@
+//! A spinning text cube
+//! 
+//! 
+//! 4    +------+  6
+//!     /|     /| 
+//! 5  +------+ |  7
+//!    | |    | | 
+//! 0  | +----|-+  2
+//!    |/     |/   
+//! 1  +------+    3
+
+pub mod controls;
+pub mod export;
+pub mod input;
+pub mod obj;
+pub mod quat;
+pub mod record;
+pub mod scene;
+pub mod stats;
+pub mod term;
+pub mod timing;
+
+/// A 4x4 matrix, stored as an array of its four columns
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Matrix(pub [[f32; 4]; 4]);
+
+/// A point or direction in homogeneous coordinates (x, y, z, w)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vector(pub [f32; 4]);
+
+/// The identity matrix, which leaves every vector unchanged
+pub const IDENTITY : Matrix = Matrix([
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+]);
+
+/// Vertices of a cube in 3D space
+pub const VERTICES : [Vector; 8] = [    Vector([-1.0, -1.0, -1.0, 1.0]),
+    Vector([-1.0, -1.0,  1.0, 1.0]),
+    Vector([ 1.0, -1.0, -1.0, 1.0]),
+    Vector([ 1.0, -1.0,  1.0, 1.0]),
+    Vector([-1.0,  1.0, -1.0, 1.0]),
+    Vector([-1.0,  1.0,  1.0, 1.0]),
+    Vector([ 1.0,  1.0, -1.0, 1.0]),
+    Vector([ 1.0,  1.0,  1.0, 1.0]),
+];
+
+/// Indices of the vertices that make up each face of the cube
+pub const FACES : [[u8; 4]; 6] = [    [1, 5, 7, 3],
+    [3, 7, 6, 2],
+    [0, 4, 5, 1],
+    [2, 6, 4, 0],
+    [0, 1, 3, 2],
+    [5, 4, 6, 7],
+];
+
+/// A shape made of flat faces, such as the cube or a model loaded with obj::parse_obj
+#[derive(Debug, Clone)]
+pub struct Mesh {
+    /// Positions of the corners of the shape
+    pub vertices: Vec<Vector>,
+    /// Indices into vertices of the corners of each face, at least three of them, wound clockwise when seen from outside
+    pub faces: Vec<Vec<usize>>,
+}
+
+impl Mesh {
+    /// The cube made of VERTICES and FACES
+    pub fn cube() -> Self {
+        Mesh {
+            vertices: VERTICES.to_vec(),
+            faces: FACES.iter().map(|face| face.iter().map(|&i| i as usize).collect()).collect(),
+        }
+    }
+
+    /// A regular tetrahedron, with its corners on four of the corners of the cube
+    pub fn tetrahedron() -> Self {
+        Mesh {
+            vertices: vec![
+                Vector([ 1.0,  1.0,  1.0, 1.0]),
+                Vector([-1.0, -1.0,  1.0, 1.0]),
+                Vector([-1.0,  1.0, -1.0, 1.0]),
+                Vector([ 1.0, -1.0, -1.0, 1.0]),
+            ],
+            faces: vec![vec![0, 1, 2], vec![3, 1, 0], vec![0, 2, 3], vec![3, 2, 1]],
+        }
+    }
+
+    /// A regular octahedron, with its corners on the axes as far from the center as the corners of the cube
+    pub fn octahedron() -> Self {
+        let r = 3.0f32.sqrt();
+        Mesh {
+            vertices: vec![
+                Vector([   r, 0.0, 0.0, 1.0]),
+                Vector([  -r, 0.0, 0.0, 1.0]),
+                Vector([ 0.0,   r, 0.0, 1.0]),
+                Vector([ 0.0,  -r, 0.0, 1.0]),
+                Vector([ 0.0, 0.0,   r, 1.0]),
+                Vector([ 0.0, 0.0,  -r, 1.0]),
+            ],
+            faces: vec![
+                vec![4, 2, 0], vec![0, 2, 5], vec![0, 3, 4], vec![5, 3, 0],
+                vec![1, 2, 4], vec![5, 2, 1], vec![4, 3, 1], vec![1, 3, 5],
+            ],
+        }
+    }
+
+    /// A regular icosahedron, with its corners as far from the center as the corners of the cube
+    pub fn icosahedron() -> Self {
+        // The corners of an icosahedron with edges of length 2 are the cyclic permutations of (0, ±1, ±phi), where phi is the golden ratio.
+        let phi = (1.0 + 5.0f32.sqrt()) / 2.0;
+        let r = 3.0f32.sqrt() / (1.0 + phi * phi).sqrt();
+        let (a, b) = (r, r * phi);
+        Mesh {
+            vertices: vec![
+                Vector([ 0.0,   a,   b, 1.0]),
+                Vector([ 0.0,   a,  -b, 1.0]),
+                Vector([ 0.0,  -a,   b, 1.0]),
+                Vector([ 0.0,  -a,  -b, 1.0]),
+                Vector([   a,   b, 0.0, 1.0]),
+                Vector([   a,  -b, 0.0, 1.0]),
+                Vector([  -a,   b, 0.0, 1.0]),
+                Vector([  -a,  -b, 0.0, 1.0]),
+                Vector([   b, 0.0,   a, 1.0]),
+                Vector([   b, 0.0,  -a, 1.0]),
+                Vector([  -b, 0.0,   a, 1.0]),
+                Vector([  -b, 0.0,  -a, 1.0]),
+            ],
+            faces: vec![
+                vec![8, 2, 0], vec![0, 2, 10], vec![6, 4, 0], vec![0, 4, 8], vec![10, 6, 0],
+                vec![1, 3, 9], vec![11, 3, 1], vec![1, 4, 6], vec![9, 4, 1], vec![1, 6, 11],
+                vec![2, 5, 7], vec![8, 5, 2], vec![2, 7, 10], vec![7, 5, 3], vec![3, 5, 9],
+                vec![11, 7, 3], vec![9, 8, 4], vec![5, 8, 9], vec![6, 10, 11], vec![11, 10, 7],
+            ],
+        }
+    }
+
+    /// The corners of the smallest box lined up with the axes that holds every vertex, the one with the lowest coordinates first, or None if there are no vertices
+    pub fn bounds(&self) -> Option<(Vector, Vector)> {
+        let first = *self.vertices.first()?;
+        Some(self.vertices.iter().fold((first, first), |(mut low, mut high), v| {
+            for axis in 0..3 {
+                low.0[axis] = low.0[axis].min(v.0[axis]);
+                high.0[axis] = high.0[axis].max(v.0[axis]);
+            }
+            (low, high)
+        }))
+    }
+
+    /// How far the farthest vertex is from the origin, which is the radius of a sphere around the origin that holds the mesh however it is turned
+    pub fn radius(&self) -> f32 {
+        self.vertices.iter().map(length).fold(0.0, f32::max)
+    }
+
+    /// Builds a transformation that moves the center of the bounds of the mesh to the origin and scales it so that its farthest vertex from there is FIT_RADIUS away, like a corner of the cube. A mesh loaded from a file can then be spun around its middle and seen whole like the built-in shapes. The mesh is only moved if it has no size.
+    pub fn fit_transform(&self) -> Matrix {
+        let Some((low, high)) = self.bounds() else {
+            return IDENTITY;
+        };
+        let center = Vector(add(&low, &high).0.map(|c| c / 2.0));
+        let radius = self.vertices.iter().map(|v| length(&sub(v, &center))).fold(0.0, f32::max);
+        let size = if radius < EPSILON { 1.0 } else { FIT_RADIUS / radius };
+        scale(size, size, size) * translation(-center.0[0], -center.0[1], -center.0[2])
+    }
+}
+
+/// How far the farthest vertex of a mesh is from the origin after Mesh::fit_transform, the same as the corners of the cube
+pub const FIT_RADIUS : f32 = 1.732_050_8;
+
+/// Performs a matrix-vector multiplication
+pub fn matrix_times_vector(m: &Matrix, v: &Vector) -> Vector {
+    let [mx, my, mz, mw] = &m.0;
+    let [x, y, z, w] = v.0;
+    // The product is the weighted sum of the columns.
+    Vector([
+        x * mx[0] + y * my[0] + z * mz[0] + w * mw[0],
+        x * mx[1] + y * my[1] + z * mz[1] + w * mw[1],
+        x * mx[2] + y * my[2] + z * mz[2] + w * mw[2],
+        x * mx[3] + y * my[3] + z * mz[3] + w * mw[3],
+    ])
+}
+
+/// Performs a matrix-matrix multiplication, so that applying the product is the same as applying b and then a
+pub fn matrix_times_matrix(a: &Matrix, b: &Matrix) -> Matrix {
+    // Each column of the product is a applied to the corresponding column of b.
+    Matrix(b.0.map(|column| matrix_times_vector(a, &Vector(column)).0))
+}
+
+/// Swaps the rows and columns of a matrix. Since matrices are stored by column, the transpose of a matrix holds its rows, which is how matrices written as rows, as they are in most texts, can be brought into this layout.
+pub fn transpose(m: &Matrix) -> Matrix {
+    Matrix(std::array::from_fn(|column| std::array::from_fn(|row| m.0[row][column])))
+}
+
+/// Computes the determinant of a matrix by reducing it to upper triangular form, when it is the product of the diagonal
+pub fn determinant(m: &Matrix) -> f32 {
+    // Rows of the matrix, which are easier to work with here than its columns
+    let mut rows = transpose(m).0;
+    let mut det = 1.0;
+    for i in 0..4 {
+        // The row with the largest element in column i becomes row i, which keeps rounding errors small. Swapping two rows changes the sign of the determinant.
+        let pivot = (i..4).max_by(|&a, &b| rows[a][i].abs().total_cmp(&rows[b][i].abs())).unwrap();
+        if rows[pivot][i] == 0.0 {
+            return 0.0;
+        }
+        if pivot != i {
+            rows.swap(pivot, i);
+            det = -det;
+        }
+        det *= rows[i][i];
+        let pivot_row = rows[i];
+        for row in &mut rows[i + 1..] {
+            let factor = row[i] / pivot_row[i];
+            for (element, pivot_element) in row.iter_mut().zip(pivot_row) {
+                *element -= factor * pivot_element;
+            }
+        }
+    }
+    det
+}
+
+/// Computes the inverse of a matrix by Gauss-Jordan elimination, or returns None if it is singular, meaning that it squashes space flat, as a scale by zero does, and cannot be undone
+pub fn inverse(m: &Matrix) -> Option<Matrix> {
+    // The rows of the matrix are reduced to those of the identity, and the same row operations turn the rows of the identity, alongside, into those of the inverse.
+    let mut rows = transpose(m).0;
+    let mut inverse = IDENTITY.0;
+    for i in 0..4 {
+        let pivot = (i..4).max_by(|&a, &b| rows[a][i].abs().total_cmp(&rows[b][i].abs())).unwrap();
+        if rows[pivot][i].abs() < EPSILON {
+            return None;
+        }
+        rows.swap(pivot, i);
+        inverse.swap(pivot, i);
+        let recip = 1.0 / rows[i][i];
+        for k in 0..4 {
+            rows[i][k] *= recip;
+            inverse[i][k] *= recip;
+        }
+        for j in (0..4).filter(|&j| j != i) {
+            let factor = rows[j][i];
+            for k in 0..4 {
+                rows[j][k] -= factor * rows[i][k];
+                inverse[j][k] -= factor * inverse[i][k];
+            }
+        }
+    }
+    Some(transpose(&Matrix(inverse)))
+}
+
+impl std::ops::Mul<Vector> for Matrix {
+    type Output = Vector;
+
+    fn mul(self, v: Vector) -> Vector {
+        matrix_times_vector(&self, &v)
+    }
+}
+
+impl std::ops::Mul<Matrix> for Matrix {
+    type Output = Matrix;
+
+    fn mul(self, b: Matrix) -> Matrix {
+        matrix_times_matrix(&self, &b)
+    }
+}
+
+/// Elements are indexed by (row, column), as in mathematical notation, even though they are stored column by column, so m[(r, c)] is m.0[c][r].
+impl std::ops::Index<(usize, usize)> for Matrix {
+    type Output = f32;
+
+    fn index(&self, (row, column): (usize, usize)) -> &f32 {
+        &self.0[column][row]
+    }
+}
+
+impl std::ops::IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut f32 {
+        &mut self.0[column][row]
+    }
+}
+
+/// Components are indexed in the order x, y, z, w.
+impl std::ops::Index<usize> for Vector {
+    type Output = f32;
+
+    fn index(&self, i: usize) -> &f32 {
+        &self.0[i]
+    }
+}
+
+impl std::ops::IndexMut<usize> for Vector {
+    fn index_mut(&mut self, i: usize) -> &mut f32 {
+        &mut self.0[i]
+    }
+}
+
+/// Computes the dot product of the x, y and z components of two vectors
+pub fn dot(a: &Vector, b: &Vector) -> f32 {
+    let [ax, ay, az, _] = a.0;
+    let [bx, by, bz, _] = b.0;
+    ax * bx + ay * by + az * bz
+}
+
+/// Computes the cross product of the x, y and z components of two vectors. The result is a direction, so its w component is 0.
+pub fn cross(a: &Vector, b: &Vector) -> Vector {
+    let [ax, ay, az, _] = a.0;
+    let [bx, by, bz, _] = b.0;
+    Vector([
+        ay * bz - az * by,
+        az * bx - ax * bz,
+        ax * by - ay * bx,
+        0.0,
+    ])
+}
+
+/// Adds two vectors. The w components are added too, so a point plus a direction is a point.
+pub fn add(a: &Vector, b: &Vector) -> Vector {
+    let [ax, ay, az, aw] = a.0;
+    let [bx, by, bz, bw] = b.0;
+    Vector([ax + bx, ay + by, az + bz, aw + bw])
+}
+
+/// Subtracts b from a. The w components are subtracted too, so the difference of two points is a direction.
+pub fn sub(a: &Vector, b: &Vector) -> Vector {
+    let [ax, ay, az, aw] = a.0;
+    let [bx, by, bz, bw] = b.0;
+    Vector([ax - bx, ay - by, az - bz, aw - bw])
+}
+
+/// Multiplies the x, y and z components of a vector by s, leaving w unchanged
+pub fn scale_vec(v: &Vector, s: f32) -> Vector {
+    let [x, y, z, w] = v.0;
+    Vector([x * s, y * s, z * s, w])
+}
+
+/// Computes the Euclidean length of the x, y and z components of a vector
+pub fn length(v: &Vector) -> f32 {
+    dot(v, v).sqrt()
+}
+
+/// Vectors shorter than this are treated as having no direction
+const EPSILON : f32 = 1e-6;
+
+/// Scales the x, y and z components of a vector to unit length. Vectors that are too short to have a direction become the zero vector. The w component is left unchanged.
+pub fn normalize(v: &Vector) -> Vector {
+    let [x, y, z, w] = v.0;
+    let len = length(v);
+    if len < EPSILON {
+        return Vector([0.0, 0.0, 0.0, w]);
+    }
+    let recip_len = 1.0 / len;
+    Vector([x * recip_len, y * recip_len, z * recip_len, w])
+}
+
+/// Whether every component of a differs from the same component of b by at most eps. Results of floating-point arithmetic are rarely exactly equal to what they should be, so they are better compared with this than with ==.
+pub fn approx_eq(a: &Vector, b: &Vector, eps: f32) -> bool {
+    a.0.iter().zip(&b.0).all(|(a, b)| (a - b).abs() <= eps)
+}
+
+/// Whether every element of a differs from the same element of b by at most eps, like approx_eq for vectors
+pub fn matrix_approx_eq(a: &Matrix, b: &Matrix, eps: f32) -> bool {
+    a.0.iter().zip(&b.0).all(|(a, b)| approx_eq(&Vector(*a), &Vector(*b), eps))
+}
+
+/// Builds a matrix that moves points by (dx, dy, dz)
+pub fn translation(dx: f32, dy: f32, dz: f32) -> Matrix {
+    Matrix([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        // The last column is added to every point, scaled by its w component.
+        [ dx,  dy,  dz, 1.0],
+    ])
+}
+
+/// Builds a matrix that stretches points by sx, sy and sz along the x, y and z axes
+pub fn scale(sx: f32, sy: f32, sz: f32) -> Matrix {
+    Matrix([
+        [ sx, 0.0, 0.0, 0.0],
+        [0.0,  sy, 0.0, 0.0],
+        [0.0, 0.0,  sz, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Builds a perspective projection matrix for a viewer looking down the -z axis. fov_y is the vertical field of view in radians and aspect is the width of the view divided by its height. After the perspective divide, points between the near and far planes have z between -1 and 1.
+pub fn perspective(fov_y: f32, aspect: f32, near: f32, far: f32) -> Matrix {
+    let f = 1.0 / (fov_y * 0.5).tan();
+    let range_recip = 1.0 / (near - far);
+    Matrix([
+        [f / aspect, 0.0, 0.0, 0.0],
+        [0.0, f, 0.0, 0.0],
+        // The w component of the result is -z, the distance in front of the viewer.
+        [0.0, 0.0, (far + near) * range_recip, -1.0],
+        [0.0, 0.0, 2.0 * far * near * range_recip, 0.0],
+    ])
+}
+
+/// Builds an orthographic projection matrix for a viewer looking down the -z axis. height is the height of the view in world units and aspect is the width of the view divided by its height. Distance does not change the size of objects, so parallel edges stay parallel.
+pub fn orthographic(height: f32, aspect: f32, near: f32, far: f32) -> Matrix {
+    let sy = 2.0 / height;
+    let range_recip = 1.0 / (near - far);
+    Matrix([
+        [sy / aspect, 0.0, 0.0, 0.0],
+        [0.0, sy, 0.0, 0.0],
+        // The w component of the result stays 1, so the perspective divide has no effect.
+        [0.0, 0.0, 2.0 * range_recip, 0.0],
+        [0.0, 0.0, (far + near) * range_recip, 1.0],
+    ])
+}
+
+/// Builds a matrix that rotates by theta radians around the x-axis, turning +Y towards +Z
+pub fn rotation_x(theta: f32) -> Matrix {
+    let (c, s) = (theta.cos(), theta.sin());
+    Matrix([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0,   c,   s, 0.0],
+        [0.0,  -s,   c, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Builds a matrix that rotates by theta radians around the y-axis, turning +X towards +Z
+pub fn rotation_y(theta: f32) -> Matrix {
+    let (c, s) = (theta.cos(), theta.sin());
+    Matrix([
+        [  c, 0.0,   s, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [ -s, 0.0,   c, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Builds a matrix that rotates by theta radians around the z-axis, turning +X towards +Y
+pub fn rotation_z(theta: f32) -> Matrix {
+    let (c, s) = (theta.cos(), theta.sin());
+    Matrix([
+        [  c,   s, 0.0, 0.0],
+        [ -s,   c, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Builds a matrix that rotates by theta radians around an axis through the origin, using the Rodrigues formula. The axis does not need to be of unit length. The rotation goes the same way as rotation_y, which for the y-axis turns +X towards +Z, so it is clockwise when seen looking back down the axis. That is the opposite of rotation_x and rotation_z, so around the x- and z-axes it equals them turning by -theta.
+pub fn rotation_axis(axis: &Vector, theta: f32) -> Matrix {
+    let [x, y, z, _] = normalize(axis).0;
+    let (c, s) = (theta.cos(), theta.sin());
+    let t = 1.0 - c;
+    // The sum of c times the identity, t times the outer product of the axis with itself, and s times the matrix that takes the cross product with the axis, subtracted to turn clockwise.
+    Matrix([
+        [c + x * x * t, x * y * t - z * s, x * z * t + y * s, 0.0],
+        [x * y * t + z * s, c + y * y * t, y * z * t - x * s, 0.0],
+        [x * z * t - y * s, y * z * t + x * s, c + z * z * t, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Angle at time t of something rocking back and forth by up to amplitude either way, frequency radians of its cycle per unit of time. It slows down smoothly towards each end of its swing, where the angle is ±amplitude, and moves fastest through zero.
+pub fn oscillation(t: f32, amplitude: f32, frequency: f32) -> f32 {
+    amplitude * (t * frequency).sin()
+}
+
+/// Builds a view matrix for a camera at eye looking towards target, with up pointing roughly towards the top of the screen. It moves and turns the world so that the camera ends up at the origin looking down the -z axis, the way the projections expect.
+pub fn look_at(eye: &Vector, target: &Vector, up: &Vector) -> Matrix {
+    let forward = normalize(&sub(target, eye));
+    let right = normalize(&cross(&forward, up));
+    let up = cross(&right, &forward);
+    let [r, u, f] = [right.0, up.0, forward.0];
+    // The rows of the rotation are the camera's axes, and the translation moves the eye to the origin.
+    Matrix([
+        [r[0], u[0], -f[0], 0.0],
+        [r[1], u[1], -f[1], 0.0],
+        [r[2], u[2], -f[2], 0.0],
+        [-dot(&right, eye), -dot(&up, eye), dot(&forward, eye), 1.0],
+    ])
+}
+
+/// Cuts off the parts of a polygon on the ground that lie outside the square of ground, keeping its corners in order
+fn clip_to_ground(mut polygon: Vec<Vector>) -> Vec<Vector> {
+    // The polygon is clipped to each side of the square in turn, keeping the corners inside it and adding corners where its edges cross the side.
+    for (axis, side) in [(0, -1.0), (0, 1.0), (2, -1.0), (2, 1.0)] {
+        let inside = |v: &Vector| v.0[axis] * side <= GROUND_SIZE;
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+        for (i, current) in polygon.iter().enumerate() {
+            let previous = &polygon[(i + polygon.len() - 1) % polygon.len()];
+            if inside(current) != inside(previous) {
+                let t = (side * GROUND_SIZE - previous.0[axis]) / (current.0[axis] - previous.0[axis]);
+                clipped.push(add(previous, &scale_vec(&sub(current, previous), t)));
+            }
+            if inside(current) {
+                clipped.push(*current);
+            }
+        }
+        polygon = clipped;
+    }
+    polygon
+}
+
+/// Builds a matrix that flattens everything onto the horizontal plane at the given height, moving each point along the light direction, which points towards a distant light. This is where the shadow of a point falls on the plane. The light must not be horizontal, or its shadows would never reach the plane.
+pub fn shadow_matrix(light: &Vector, height: f32) -> Matrix {
+    let [x, y, z, _] = light.0;
+    let (dx, dz) = (x / y, z / y);
+    Matrix([
+        [1.0, 0.0, 0.0, 0.0],
+        [-dx, 0.0, -dz, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [dx * height, height, dz * height, 1.0],
+    ])
+}
+
+/// Default width of the screen in characters
+pub const SCREEN_WIDTH : usize = 80;
+
+/// Default height of the screen in characters
+pub const SCREEN_HEIGHT : usize = 40;
+
+/// The kind of projection used to map the world onto the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Projection {
+    /// Distant objects appear smaller
+    Perspective,
+    /// Objects keep their size regardless of distance
+    Orthographic,
+}
+
+/// Which faces render_frame leaves out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CullMode {
+    /// Leave out the faces that face away from the viewer
+    Back,
+    /// Leave out the faces that face the viewer
+    Front,
+    /// Draw every face, which is useful for debugging
+    None,
+}
+
+/// What render_frame draws of a mesh
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum RenderMode {
+    /// The edges of the visible faces
+    Wireframe,
+    /// The visible faces, filled with the fill character of the charset
+    Filled,
+    /// The visible faces, filled with a character from SHADES for how directly they face the light
+    Shaded,
+    /// The visible faces, filled with a character from SHADES for how brightly they are lit by phong, with a highlight where they reflect the light towards the camera
+    Phong,
+    /// The visible faces, filled with the fill character of the charset in the 24-bit color normal_color gives for the direction they face in the coordinates of the mesh, so that each face keeps its color as it turns. This shows whether the normals of a mesh are right.
+    Normals,
+    /// Only the vertices of the visible faces, marked with the vertex character of the charset
+    Points,
+}
+
+/// Parses the name of a render mode, as used by --mode
+impl std::str::FromStr for RenderMode {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "wireframe" => Ok(RenderMode::Wireframe),
+            "filled" => Ok(RenderMode::Filled),
+            "shaded" => Ok(RenderMode::Shaded),
+            "phong" => Ok(RenderMode::Phong),
+            "normals" => Ok(RenderMode::Normals),
+            "points" => Ok(RenderMode::Points),
+            _ => Err(format!("unknown render mode '{}'", name)),
+        }
+    }
+}
+
+/// Settings that control how render_frame draws a mesh
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// The projection used to map the world onto the screen
+    pub projection: Projection,
+    /// Transformation from world coordinates into the coordinates of the camera, such as from look_at. The camera looks down the -z axis of its coordinates.
+    pub view: Matrix,
+    /// Vertical field of view in radians, used by the perspective projection
+    pub fov_y: f32,
+    /// Height of the view in world units, used by the orthographic projection
+    pub view_height: f32,
+    /// Width of a terminal cell divided by its height. Cells are usually about twice as tall as they are wide, and the view is stretched to make up for it, so that the cube looks square.
+    pub cell_aspect: f32,
+    /// Distance from the viewer to the near clipping plane
+    pub near: f32,
+    /// Distance from the viewer to the far clipping plane
+    pub far: f32,
+    /// Whether the mesh is drawn as a wireframe, with filled or shaded faces, or as points
+    pub mode: RenderMode,
+    /// Characters the edges, vertices and faces are drawn with
+    pub charset: Charset,
+    /// Which faces are left out
+    pub cull_mode: CullMode,
+    /// Whether the depth buffer decides which of overlapping lines and faces is drawn. Without it, filled faces still overlap correctly on convex shapes, since they are drawn from back to front.
+    pub depth_test: bool,
+    /// If set, each face is drawn in one of these 256-color terminal colors, going through them in order of the faces of the mesh, so that the faces of the cube get the colors with the same index as the face in FACES
+    pub colors: Option<[u8; 6]>,
+    /// Whether the edges of the wireframe are drawn with characters from DEPTH_CUE instead of the charset, denser where they are nearer the camera
+    pub depth_cue: bool,
+    /// Whether the edges of the wireframe are antialiased with draw_antialiased_line, which draws them with characters from ANTIALIAS_SHADES for how much of each cell they cover. This takes the place of depth cueing.
+    pub antialias: bool,
+    /// Whether the edges of the wireframe are broken by HALO_WIDTH cells either side of where they pass behind nearer edges, as in a hidden-line drawing, so that it is clear which edge is in front. This has no effect on antialiased edges.
+    pub halo: bool,
+    /// Whether the x, y and z axes of each model are drawn as short lines from its origin, in red, green and blue
+    pub axes: bool,
+    /// Character the cells that nothing is drawn into are filled with
+    pub background: char,
+    /// Whether each vertex is marked with the vertex character of the charset
+    pub vertex_markers: bool,
+    /// Whether the index of each vertex in its mesh is written just right of it
+    pub vertex_labels: bool,
+    /// The lights that shade the faces in RenderMode::Shaded and RenderMode::Phong, whose light is added up. If there are none, a single light of full intensity from DEFAULT_LIGHT is used.
+    pub lights: Vec<Light>,
+    /// How shiny the faces are in RenderMode::Phong, where higher values give a smaller, sharper highlight
+    pub shininess: f32,
+    /// Whether a square of ground is drawn below the models with their shadows cast onto it by the first light
+    pub shadow: bool,
+}
+
+/// The characters render_frame draws with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Charset {
+    /// Character for lines closer to horizontal than diagonal
+    pub horizontal: char,
+    /// Character for lines closer to vertical than diagonal
+    pub vertical: char,
+    /// Character for diagonal lines going up to the right
+    pub rising: char,
+    /// Character for diagonal lines going down to the right
+    pub falling: char,
+    /// Character that marks vertices
+    pub vertex: char,
+    /// Character that filled faces are drawn with when they are not shaded
+    pub fill: char,
+}
+
+impl Default for Charset {
+    fn default() -> Self {
+        Charset {
+            horizontal: '-',
+            vertical: '|',
+            rising: '/',
+            falling: '\\',
+            vertex: '*',
+            fill: '#',
+        }
+    }
+}
+
+impl std::str::FromStr for Charset {
+    type Err = String;
+
+    /// Parses a charset from its six characters in the order of the fields, like "-|/\\*#" for the default one
+    fn from_str(chars: &str) -> Result<Self, Self::Err> {
+        match *chars.chars().collect::<Vec<char>>() {
+            [horizontal, vertical, rising, falling, vertex, fill] => Ok(Charset { horizontal, vertical, rising, falling, vertex, fill }),
+            _ => Err(format!("a charset needs six characters, not '{}'", chars)),
+        }
+    }
+}
+
+/// A palette of distinct 256-color terminal colors for the six faces: red, green, blue, yellow, magenta and cyan
+pub const FACE_COLORS : [u8; 6] = [196, 46, 21, 226, 201, 51];
+
+/// Direction of the light when none is given, from the upper left of the front of the scene
+pub const DEFAULT_LIGHT : Vector = Vector([-1.0, 1.0, 1.0, 0.0]);
+
+/// A directional light, which lights the whole scene from the same direction like a distant sun
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Light {
+    /// Direction from the mesh towards the light in world coordinates
+    pub direction: Vector,
+    /// How bright the light is, where 1 lights a face facing it fully on its own
+    pub intensity: f32,
+}
+
+impl Light {
+    /// A light of full intensity from the given direction
+    pub fn new(direction: Vector) -> Self {
+        Light { direction, intensity: 1.0 }
+    }
+}
+
+impl std::str::FromStr for Light {
+    type Err = String;
+
+    /// Parses a light from its direction and optionally its intensity, separated by commas, like "-1,1,1" or "0,1,0,0.5"
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let numbers: Vec<f32> = text.split(',').map(|number| number.trim().parse()).collect::<Result<_, _>>().map_err(|_| format!("'{}' is not a light", text))?;
+        match *numbers {
+            [x, y, z] => Ok(Light::new(Vector([x, y, z, 0.0]))),
+            [x, y, z, intensity] => Ok(Light { direction: Vector([x, y, z, 0.0]), intensity }),
+            _ => Err(format!("a light needs a direction x,y,z and optionally an intensity, not '{}'", text)),
+        }
+    }
+}
+
+/// Fill characters used for shading, from darkest to brightest
+pub const SHADES : &[u8] = b" .:-=+*#%@";
+
+/// Picks the shading character for a face with the given outward normal, such as from face_normal, lit from the given direction. Faces turned away from the light get the darkest character.
+pub fn shade(normal: &Vector, light: &Vector) -> char {
+    shade_glyph(diffuse_lighting(normal, &[Light::new(*light)]))
+}
+
+/// Brightness of a face with the given outward normal from the light of all the given lights, each of which lights it by its intensity times how directly the face faces it. Lights behind the face add nothing.
+pub fn diffuse_lighting(normal: &Vector, lights: &[Light]) -> f32 {
+    let normal = normalize(normal);
+    lights.iter().map(|light| light.intensity * dot(&normal, &normalize(&light.direction)).max(0.0)).sum()
+}
+
+/// Height of the ground that shadows are cast onto, just below the cube however it is turned
+pub const GROUND_HEIGHT : f32 = -1.8;
+
+/// Half the width of the square of ground, centered below the origin
+pub const GROUND_SIZE : f32 = 2.0;
+
+/// Character the ground is filled with
+pub const GROUND : char = ':';
+
+/// Dim character that shadows on the ground are filled with
+pub const SHADOW : char = '.';
+
+/// How far above the ground shadows are drawn, so that they are nearer than the ground and cover it in the depth buffer
+const SHADOW_OFFSET : f32 = 0.01;
+
+/// How much light a face reflects in RenderMode::Phong whichever way it faces
+pub const AMBIENT : f32 = 0.1;
+
+/// How much of the light falling straight onto a face it scatters in every direction in RenderMode::Phong
+pub const DIFFUSE : f32 = 0.6;
+
+/// How much of the light a face reflects like a mirror in RenderMode::Phong, which gives the highlight
+pub const SPECULAR : f32 = 0.6;
+
+/// Brightness of a face with the given outward normal by the Phong reflection model, lit from the given direction and seen from the given direction, which points from the face towards the viewer. It is the sum of the ambient light, the diffuse light for how directly the face faces the light, and the specular highlight for how directly it reflects the light towards the viewer, which is narrower the higher the shininess. Faces turned away from the light only get the ambient light.
+pub fn phong(normal: &Vector, light: &Vector, view: &Vector, shininess: f32) -> f32 {
+    phong_lighting(normal, &[Light::new(*light)], view, shininess)
+}
+
+/// Brightness of a face like phong, but lit by all the given lights, which each add their diffuse light and highlight times their intensity to the ambient light
+pub fn phong_lighting(normal: &Vector, lights: &[Light], view: &Vector, shininess: f32) -> f32 {
+    let (normal, view) = (normalize(normal), normalize(view));
+    let lit: f32 = lights.iter().map(|light| {
+        let direction = normalize(&light.direction);
+        let diffuse = dot(&normal, &direction);
+        if diffuse <= 0.0 {
+            return 0.0;
+        }
+        // The light is reflected about the normal, so the reflection leaves the face as far on the other side of the normal as the light comes in.
+        let reflection = sub(&scale_vec(&normal, 2.0 * diffuse), &direction);
+        let specular = dot(&reflection, &view).max(0.0).powf(shininess);
+        light.intensity * (DIFFUSE * diffuse + SPECULAR * specular)
+    }).sum();
+    AMBIENT + lit
+}
+
+/// The 24-bit color that shows the direction of a normal in RenderMode::Normals. Each of the x, y and z components of the normal, once it has been normalized, goes from -1 to 1 and gives the red, green and blue components from 0 to 255, so a face facing +x is pinkish red, one facing +y light green and one facing +z light blue.
+pub fn normal_color(normal: &Vector) -> [u8; 3] {
+    let Vector([x, y, z, _]) = normalize(normal);
+    [x, y, z].map(|c| ((c + 1.0) / 2.0 * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+/// Picks the shading character for a face like shade, but for its brightness by phong
+pub fn phong_shade(normal: &Vector, light: &Vector, view: &Vector, shininess: f32) -> char {
+    shade_glyph(phong(normal, light, view, shininess))
+}
+
+/// The character from SHADES for a brightness from 0 to 1, where anything brighter gets the brightest one
+pub fn shade_glyph(intensity: f32) -> char {
+    SHADES[(intensity.clamp(0.0, 1.0) * (SHADES.len() - 1) as f32).round() as usize] as char
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            projection: Projection::Perspective,
+            view: IDENTITY,
+            fov_y: std::f32::consts::FRAC_PI_2,
+            view_height: 5.0,
+            cell_aspect: 0.5,
+            near: 0.1,
+            far: 100.0,
+            mode: RenderMode::Wireframe,
+            charset: Charset::default(),
+            depth_cue: false,
+            antialias: false,
+            halo: false,
+            axes: false,
+            background: ' ',
+            vertex_markers: false,
+            vertex_labels: false,
+            cull_mode: CullMode::Back,
+            depth_test: true,
+            lights: Vec::new(),
+            shininess: 16.0,
+            shadow: false,
+            colors: None,
+        }
+    }
+}
+
+/// Color of what the left eye sees in render_anaglyph_into: red, which a red filter over the left eye lets through
+pub const ANAGLYPH_LEFT : u8 = 196;
+
+/// Color of what the right eye sees in render_anaglyph_into: cyan, which a cyan filter over the right eye lets through
+pub const ANAGLYPH_RIGHT : u8 = 51;
+
+/// Color of what both eyes see in the same cell in render_anaglyph_into: white, which both filters let through
+pub const ANAGLYPH_BOTH : u8 = 231;
+
+/// How far from the origin the cameras of the orthographic views of render_views_into are
+const VIEW_DISTANCE : f32 = 5.0;
+
+/// Where the cameras of the orthographic views of render_views_into are, looking at the origin, along with the way up on the screen for each: the front, the top and the right side
+pub const VIEWS : [(Vector, Vector); 3] = [
+    (Vector([0.0, 0.0, 1.0, 0.0]), Vector([0.0, 1.0, 0.0, 0.0])),
+    (Vector([0.0, 1.0, 0.0, 0.0]), Vector([0.0, 0.0, -1.0, 0.0])),
+    (Vector([1.0, 0.0, 0.0, 0.0]), Vector([0.0, 1.0, 0.0, 0.0])),
+];
+
+/// Applies a projection matrix to a point and performs the perspective divide, giving normalized device coordinates. Returns None for a point in the plane of the viewer or behind it, where w is zero or negative and the divide would give infinite or mirrored coordinates.
+pub fn project(projection: &Matrix, v: &Vector) -> Option<Vector> {
+    let [x, y, z, w] = matrix_times_vector(projection, v).0;
+    if w.is_nan() || w < EPSILON {
+        return None;
+    }
+    let recip_w = 1.0 / w;
+    Some(Vector([x * recip_w, y * recip_w, z * recip_w, 1.0]))
+}
+
+/// Maps normalized device coordinates onto a screen of the given size, keeping the depth as the third coordinate. The screen's y-axis points down, so it is flipped.
+pub fn viewport(ndc: &Vector, width: usize, height: usize) -> [f32; 3] {
+    let [x, y, z, _] = ndc.0;
+    // The center of the screen is also how far it extends to either side.
+    let offset_x = width as f32 * 0.5;
+    let offset_y = height as f32 * 0.5;
+    let scale_x = offset_x;
+    let scale_y = offset_y;
+    [x * scale_x + offset_x, -y * scale_y + offset_y, z]
+}
+
+/// A frame buffer holding the character drawn in each cell of the screen along with its depth and color. The cells are stored row by row.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    /// The character in each cell
+    chars: Vec<char>,
+    /// The depth of the nearest thing drawn in each cell, or infinity where nothing has been drawn
+    depth: Vec<f32>,
+    /// The 256-color terminal color of each cell, or None to use the terminal's default color
+    colors: Vec<Option<u8>>,
+    /// The 24-bit color of each cell drawn with plot_rgb, which takes the place of its 256-color one, or None for the other cells
+    rgb: Vec<Option<[u8; 3]>>,
+    /// Whether plot compares depths, or just draws over whatever was there
+    depth_test: bool,
+    /// The character empty cells are filled with
+    background: char,
+}
+
+impl Frame {
+    /// Creates an empty frame of the given size filled with spaces
+    pub fn new(width: usize, height: usize) -> Self {
+        let cells = width * height;
+        Frame {
+            width,
+            height,
+            chars: vec![' '; cells],
+            depth: vec![f32::INFINITY; cells],
+            colors: vec![None; cells],
+            rgb: vec![None; cells],
+            depth_test: true,
+            background: ' ',
+        }
+    }
+
+    /// Empties the frame again, filling it with the background character, so that it can be drawn into afresh without allocating a new one
+    pub fn clear(&mut self) {
+        self.chars.fill(self.background);
+        self.depth.fill(f32::INFINITY);
+        self.colors.fill(None);
+        self.rgb.fill(None);
+    }
+
+    /// Changes the size of the frame, which leaves it empty
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let cells = width * height;
+        self.width = width;
+        self.height = height;
+        self.chars.resize(cells, self.background);
+        self.depth.resize(cells, f32::INFINITY);
+        self.colors.resize(cells, None);
+        self.rgb.resize(cells, None);
+        self.clear();
+    }
+
+    /// Chooses whether plot only draws over cells with something farther away in them, which is the default, or over any cell
+    pub fn set_depth_test(&mut self, depth_test: bool) {
+        self.depth_test = depth_test;
+    }
+
+    /// Chooses the character that clear fills empty cells with, which is a space by default
+    pub fn set_background(&mut self, background: char) {
+        self.background = background;
+    }
+
+    /// The character empty cells are filled with
+    pub fn background(&self) -> char {
+        self.background
+    }
+
+    /// Width of the frame in characters
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the frame in characters
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the characters of a row of the frame
+    pub fn row(&self, y: usize) -> &[char] {
+        &self.chars[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Returns the colors of the cells of a row of the frame, None for cells without a color
+    pub fn row_colors(&self, y: usize) -> &[Option<u8>] {
+        &self.colors[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Returns the 24-bit colors of the cells of a row of the frame, None for cells that were not drawn with plot_rgb
+    pub fn row_rgb(&self, y: usize) -> &[Option<[u8; 3]>] {
+        &self.rgb[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Draws a character into a cell if the cell is on the screen and nothing nearer than depth z has been drawn there, or whatever has been drawn there if the depth test is off
+    pub fn plot(&mut self, x: usize, y: usize, z: f32, glyph: char, color: Option<u8>) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let i = y * self.width + x;
+        if !self.depth_test || z < self.depth[i] {
+            self.depth[i] = z;
+            self.chars[i] = glyph;
+            self.colors[i] = color;
+            self.rgb[i] = None;
+        }
+    }
+
+    /// Draws a character into a cell like plot, but in a 24-bit color, which only truecolor terminals can show
+    pub fn plot_rgb(&mut self, x: usize, y: usize, z: f32, glyph: char, rgb: [u8; 3]) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let i = y * self.width + x;
+        if !self.depth_test || z < self.depth[i] {
+            self.depth[i] = z;
+            self.chars[i] = glyph;
+            self.colors[i] = None;
+            self.rgb[i] = Some(rgb);
+        }
+    }
+
+    /// Writes text into a row of the frame starting at column x, over whatever has been drawn there, without color. The text is cut off at the right edge of the frame, and nothing drawn into the frame afterwards covers it.
+    pub fn write_text(&mut self, x: usize, y: usize, text: &str) {
+        if y >= self.height || x >= self.width {
+            return;
+        }
+        let start = y * self.width + x;
+        let mut len = 0;
+        for (cell, glyph) in self.chars[start..(y + 1) * self.width].iter_mut().zip(text.chars()) {
+            *cell = glyph;
+            len += 1;
+        }
+        self.depth[start..start + len].fill(f32::NEG_INFINITY);
+        self.colors[start..start + len].fill(None);
+        self.rgb[start..start + len].fill(None);
+    }
+
+    /// Copies another frame into this one with its top left corner at column x and row y, along with its depths and colors. Whatever does not fit is cut off at the edges of this frame.
+    pub fn blit(&mut self, x: usize, y: usize, other: &Frame) {
+        let columns = other.width.min(self.width.saturating_sub(x));
+        for row in 0..other.height.min(self.height.saturating_sub(y)) {
+            let (to, from) = ((y + row) * self.width + x, row * other.width);
+            self.chars[to..to + columns].copy_from_slice(&other.chars[from..from + columns]);
+            self.depth[to..to + columns].copy_from_slice(&other.depth[from..from + columns]);
+            self.colors[to..to + columns].copy_from_slice(&other.colors[from..from + columns]);
+            self.rgb[to..to + columns].copy_from_slice(&other.rgb[from..from + columns]);
+        }
+    }
+
+    /// Encodes a row of the frame as UTF-8 for the terminal, with ANSI escape sequences that switch to the color of each colored cell and reset the color at the end of the row. The characters are copied as they are, whatever their value.
+    pub fn encode_row(&self, y: usize, out: &mut Vec<u8>) {
+        self.encode_cells(Glyphs::Ascii, y, ColorSwitch::new(true), out);
+    }
+
+    /// Encodes a row of characters on the terminal like encode_row, but with the cells shown as the given glyphs. A row of characters covers as many rows of cells as the glyphs stack in one character.
+    pub fn encode_glyph_row(&self, glyphs: Glyphs, row: usize, out: &mut Vec<u8>) {
+        self.encode_cells(glyphs, row, ColorSwitch::new(true), out);
+    }
+
+    /// Encodes a row of characters like encode_glyph_row, but without any escape sequences, so that the text has no colors and can be read anywhere, such as in a log file
+    pub fn encode_plain_glyph_row(&self, glyphs: Glyphs, row: usize, out: &mut Vec<u8>) {
+        self.encode_cells(glyphs, row, ColorSwitch::new(false), out);
+    }
+
+    /// Encodes a row of characters for encode_glyph_row and encode_plain_glyph_row, switching colors with colors
+    fn encode_cells(&self, glyphs: Glyphs, row: usize, mut colors: ColorSwitch, out: &mut Vec<u8>) {
+        match glyphs {
+            Glyphs::Ascii => {
+                for (x, &glyph) in self.row(row).iter().enumerate() {
+                    colors.switch(self.term_color(row * self.width + x), out);
+                    let mut utf8 = [0; 4];
+                    out.extend_from_slice(glyph.encode_utf8(&mut utf8).as_bytes());
+                }
+            }
+            Glyphs::HalfBlocks => {
+                for x in 0..self.width {
+                    let top = self.cell_color(x, 2 * row);
+                    let bottom = self.cell_color(x, 2 * row + 1);
+                    colors.switch(top.or(bottom).flatten(), out);
+                    let mut utf8 = [0; 4];
+                    out.extend_from_slice(half_block(top.is_some(), bottom.is_some()).encode_utf8(&mut utf8).as_bytes());
+                }
+            }
+            Glyphs::BoxDrawing => {
+                for x in 0..self.width {
+                    colors.switch(self.term_color(row * self.width + x), out);
+                    let mut utf8 = [0; 4];
+                    out.extend_from_slice(self.box_drawing_glyph(x, row).encode_utf8(&mut utf8).as_bytes());
+                }
+            }
+            Glyphs::Braille => {
+                for x in 0..self.width / 2 {
+                    let mut dots = [[false; 2]; 4];
+                    let mut color = None;
+                    for (dy, dot_row) in dots.iter_mut().enumerate() {
+                        for (dx, dot) in dot_row.iter_mut().enumerate() {
+                            if let Some(cell) = self.cell_color(2 * x + dx, 4 * row + dy) {
+                                *dot = true;
+                                color = color.or(Some(cell));
+                            }
+                        }
+                    }
+                    colors.switch(color.flatten(), out);
+                    let mut utf8 = [0; 4];
+                    out.extend_from_slice(braille(dots).encode_utf8(&mut utf8).as_bytes());
+                }
+            }
+        }
+        colors.switch(None, out);
+    }
+
+    /// Number of rows of characters it takes to show the frame as the given glyphs
+    pub fn glyph_rows(&self, glyphs: Glyphs) -> usize {
+        self.height.div_ceil(glyphs.cell_size().1)
+    }
+
+    /// Returns the box-drawing character for a cell of the frame. Lines of '-' and '|' become lines of box-drawing characters that join up where they meet. A line only joins one it runs into at its end, or one that crosses it, so that lines that merely pass next to each other stay apart. '/' and '\' become box-drawing diagonals, and everything else is shown as it is.
+    fn box_drawing_glyph(&self, x: usize, y: usize) -> char {
+        let at = |dx: isize, dy: isize| {
+            let (x, y) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+            if x < self.width && y < self.height { self.chars[y * self.width + x] } else { ' ' }
+        };
+        let [left, right] = [at(-1, 0) == '-', at(1, 0) == '-'];
+        let [up, down] = [at(0, -1) == '|', at(0, 1) == '|'];
+        match self.chars[y * self.width + x] {
+            '-' => {
+                let run_ends = !(left && right);
+                let (up, down) = if run_ends || (up && down) { (up, down) } else { (false, false) };
+                box_drawing(up, down, left || !right, right || !left)
+            }
+            '|' => {
+                let run_ends = !(up && down);
+                let (left, right) = if run_ends || (left && right) { (left, right) } else { (false, false) };
+                box_drawing(up || !down, down || !up, left, right)
+            }
+            '/' => '\u{2571}',
+            '\\' => '\u{2572}',
+            glyph => glyph,
+        }
+    }
+
+    /// Returns the color of a cell that something has been drawn into, or None for an empty cell or one outside the frame
+    fn cell_color(&self, x: usize, y: usize) -> Option<Option<TermColor>> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = y * self.width + x;
+        (self.chars[i] != self.background).then(|| self.term_color(i))
+    }
+
+    /// The color the cell with index i is shown in on the terminal: its 24-bit color if it has one, and otherwise its 256-color one
+    fn term_color(&self, i: usize) -> Option<TermColor> {
+        self.rgb[i].map(TermColor::Rgb).or(self.colors[i].map(TermColor::Indexed))
+    }
+
+    /// Converts a row of the frame into a string like encode_row
+    pub fn row_to_string(&self, y: usize) -> String {
+        let mut row = Vec::with_capacity(self.width);
+        self.encode_row(y, &mut row);
+        // encode_row only writes whole characters and escape sequences, so the row is always valid UTF-8.
+        String::from_utf8(row).unwrap()
+    }
+}
+
+/// The color of the text while a row is being encoded, which only writes an escape sequence when the color changes, and none at all if escape sequences are turned off
+struct ColorSwitch {
+    /// Whether escape sequences are written
+    enabled: bool,
+    /// The color of the text, where None is the default
+    current: Option<TermColor>,
+}
+
+impl ColorSwitch {
+    fn new(enabled: bool) -> Self {
+        ColorSwitch { enabled, current: None }
+    }
+
+    /// Writes the escape sequence that changes the color of the text to color, if it is not the current color already. None resets the color to the default.
+    fn switch(&mut self, color: Option<TermColor>, out: &mut Vec<u8>) {
+        if !self.enabled || color == self.current {
+            return;
+        }
+        match color {
+            Some(TermColor::Indexed(code)) => out.extend_from_slice(format!("\x1b[38;5;{}m", code).as_bytes()),
+            Some(TermColor::Rgb([r, g, b])) => out.extend_from_slice(format!("\x1b[38;2;{};{};{}m", r, g, b).as_bytes()),
+            None => out.extend_from_slice(b"\x1b[0m"),
+        }
+        self.current = color;
+    }
+}
+
+/// A color the text can be shown in on the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TermColor {
+    /// One of the 256 terminal colors
+    Indexed(u8),
+    /// A 24-bit color, given by its red, green and blue components
+    Rgb([u8; 3]),
+}
+
+/// How the cells of a frame are shown as characters on the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyphs {
+    /// Each cell is shown as the character drawn into it
+    Ascii,
+    /// Each character shows two cells, one above the other, as the top half, the bottom half or all of a block. Cells are square rather than twice as tall as they are wide.
+    HalfBlocks,
+    /// Each cell is shown as the character drawn into it, except that lines are drawn with box-drawing characters that join up at corners and crossings
+    BoxDrawing,
+    /// Each character shows a block of cells two across and four down as the dots of a Braille pattern. Cells are square rather than twice as tall as they are wide.
+    Braille,
+}
+
+impl Glyphs {
+    /// Number of cells across and down that each character shows
+    pub fn cell_size(self) -> (usize, usize) {
+        match self {
+            Glyphs::Ascii => (1, 1),
+            Glyphs::HalfBlocks => (1, 2),
+            Glyphs::BoxDrawing => (1, 1),
+            Glyphs::Braille => (2, 4),
+        }
+    }
+}
+
+impl std::str::FromStr for Glyphs {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "ascii" => Ok(Glyphs::Ascii),
+            "half-blocks" => Ok(Glyphs::HalfBlocks),
+            "box" => Ok(Glyphs::BoxDrawing),
+            "braille" => Ok(Glyphs::Braille),
+            _ => Err(format!("unknown glyphs '{}'", name)),
+        }
+    }
+}
+
+/// Returns the character that shows whether the top and the bottom half of a character cell are filled
+pub fn half_block(top: bool, bottom: bool) -> char {
+    match (top, bottom) {
+        (false, false) => ' ',
+        (true, false) => '\u{2580}',
+        (false, true) => '\u{2584}',
+        (true, true) => '\u{2588}',
+    }
+}
+
+/// Returns the box-drawing character with lines going from the middle of the cell towards the given sides. A cell with only one side has a line going all the way across it instead of a stub.
+pub fn box_drawing(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (false, false, false, false) => ' ',
+        (_, _, false, false) => '\u{2502}',
+        (false, false, _, _) => '\u{2500}',
+        (false, true, false, true) => '\u{250c}',
+        (false, true, true, false) => '\u{2510}',
+        (true, false, false, true) => '\u{2514}',
+        (true, false, true, false) => '\u{2518}',
+        (true, true, false, true) => '\u{251c}',
+        (true, true, true, false) => '\u{2524}',
+        (false, true, true, true) => '\u{252c}',
+        (true, false, true, true) => '\u{2534}',
+        (true, true, true, true) => '\u{253c}',
+    }
+}
+
+/// Bit of a Braille pattern for each dot, by row and then column. The first three rows are numbered down the left column and then the right one, and the bottom row was added later.
+const BRAILLE_DOTS : [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Returns the Braille pattern with the given dots raised, by row and then column
+pub fn braille(dots: [[bool; 2]; 4]) -> char {
+    let mut bits = 0;
+    for (row, bit_row) in dots.iter().zip(BRAILLE_DOTS) {
+        for (&dot, bit) in row.iter().zip(bit_row) {
+            if dot {
+                bits |= bit;
+            }
+        }
+    }
+    // Every value from U+2800 to U+28FF is a Braille pattern
+    char::from_u32(0x2800 + bits).unwrap()
+}
+
+/// Converts a whole frame into a string, one line per row, including the escape sequences for colored cells
+pub fn frame_to_string(frame: &Frame) -> String {
+    let mut text = String::with_capacity((frame.width + 1) * frame.height);
+    for y in 0..frame.height {
+        text.push_str(&frame.row_to_string(y));
+        text.push('\n');
+    }
+    text
+}
+
+/// A mesh placed in the world by a transformation matrix. Several models can share one mesh.
+#[derive(Debug, Clone, Copy)]
+pub struct Model<'a> {
+    /// The shape of the model
+    pub mesh: &'a Mesh,
+    /// Transformation from the coordinates of the mesh into world coordinates
+    pub model_to_world: Matrix,
+}
+
+///Renders one frame of a mesh transformed by model_to_world at the given size. The vertices are projected onto the screen, the faces chosen by options.cull_mode are culled and the remaining faces are drawn as options.mode chooses into the returned frame buffer.
+pub fn render_frame(mesh: &Mesh, model_to_world: &Matrix, options: &RenderOptions, width: usize, height: usize) -> Frame {
+    render_scene(&[Model { mesh, model_to_world: *model_to_world }], options, width, height)
+}
+
+/// Everything render_scene works out about the vertices and faces of a scene before drawing it
+pub(crate) struct ProjectedScene {
+    /// The projection matrix for the screen
+    pub projection: Matrix,
+    /// Positions of the vertices of each model in world coordinates
+    pub world_pos: Vec<Vec<Vector>>,
+    /// Positions of the vertices of each model in camera coordinates
+    pub view_pos: Vec<Vec<Vector>>,
+    /// Screen coordinates and depths of the vertices of each model, None for those that cannot be projected
+    pub screen_pos: Vec<Vec<Option<[f32; 3]>>>,
+    /// Whether each face of each model is drawn after culling
+    pub visible: Vec<Vec<bool>>,
+}
+
+/// Width of the view on a screen of the given size divided by its height, taking into account that cells are not square
+fn view_aspect(options: &RenderOptions, width: usize, height: usize) -> f32 {
+    width as f32 * options.cell_aspect / height as f32
+}
+
+/// Builds the projection matrix that render_scene uses for a screen of the given size
+pub fn projection_matrix(options: &RenderOptions, width: usize, height: usize) -> Matrix {
+    let aspect = view_aspect(options, width, height);
+    match options.projection {
+        Projection::Perspective => perspective(options.fov_y, aspect, options.near, options.far),
+        Projection::Orthographic => orthographic(options.view_height, aspect, options.near, options.far),
+    }
+}
+
+/// How much of the screen is left clear around a shape scaled by screen_fit_scale, as a fraction of the distance from the middle of the screen to its nearest edge
+pub const SCREEN_FIT_MARGIN : f32 = 0.1;
+
+/// Factor to scale a shape by so that it stays on a screen of the given size however it turns. The shape fits in a sphere of the given radius, whose center the camera looks straight at from distance away, and the factor makes the outline of the sphere on the screen reach no further than SCREEN_FIT_MARGIN from the nearest edge.
+pub fn screen_fit_scale(radius: f32, distance: f32, options: &RenderOptions, width: usize, height: usize) -> f32 {
+    if radius < EPSILON {
+        return 1.0;
+    }
+    // How far the view reaches from its middle to its nearer edges, either as the tangent of the angle to them or in world units
+    let reach = (1.0 - SCREEN_FIT_MARGIN) * view_aspect(options, width, height).min(1.0);
+    let fitted_radius = match options.projection {
+        // The lines of sight that touch a sphere of radius r at distance d make an angle whose sine is r / d with the line to its center, so the sphere fits if that angle is no wider than the angle to the edge.
+        Projection::Perspective => distance * (reach * (options.fov_y / 2.0).tan()).atan().sin(),
+        Projection::Orthographic => reach * options.view_height / 2.0,
+    };
+    fitted_radius / radius
+}
+
+/// Projects the vertices of a scene onto a screen of the given size and culls its faces
+pub(crate) fn project_scene(models: &[Model], options: &RenderOptions, width: usize, height: usize) -> ProjectedScene {
+    let projection = projection_matrix(options, width, height);
+
+    // Transforms the 3D positions of the vertices of each mesh into screen coordinates by applying its transformation matrix to obtain the world coordinates, applying the view matrix to obtain the coordinates seen from the camera, projecting those with the projection matrix and then mapping the result onto the 2D screen with the viewport transform, storing the resulting coordinates and depths in the screen_pos vectors. Vertices that cannot be projected because they are not in front of the camera are None.
+    let world_pos: Vec<Vec<Vector>> = models.iter().map(|model| {
+        model.mesh.vertices.iter().map(|v| matrix_times_vector(&model.model_to_world, v)).collect()
+    }).collect();
+    let view_pos: Vec<Vec<Vector>> = world_pos.iter().map(|world_pos| {
+        world_pos.iter().map(|w| matrix_times_vector(&options.view, w)).collect()
+    }).collect();
+    let screen_pos: Vec<Vec<Option<[f32; 3]>>> = view_pos.iter().map(|view_pos| {
+        view_pos.iter().map(|v| project(&projection, v).map(|ndc| viewport(&ndc, width, height))).collect()
+    }).collect();
+
+    // Uses is_back_face to determine which faces of each mesh face the viewer, and from that which should be drawn. It goes by the normal of the whole face in camera coordinates rather than the winding of its first three vertices on the screen, which can be the wrong way round for concave or bent faces of loaded meshes and makes them flicker as they turn.
+    let visible: Vec<Vec<bool>> = models.iter().zip(&view_pos).map(|(model, view_pos)| {
+        model.mesh.faces.iter().map(|face| {
+            let back = is_back_face(&face_normal(face, view_pos), &centroid(face, view_pos), options.projection);
+            match options.cull_mode {
+                CullMode::Back => !back,
+                CullMode::Front => back,
+                CullMode::None => true,
+            }
+        }).collect()
+    }).collect();
+    ProjectedScene { projection, world_pos, view_pos, screen_pos, visible }
+}
+
+///Renders one frame of a scene of several models at the given size, like render_frame does for one. The models are drawn into the same frame buffer, so with the depth buffer, or when filled, nearer ones hide the ones behind them.
+pub fn render_scene(models: &[Model], options: &RenderOptions, width: usize, height: usize) -> Frame {
+    let mut frame = Frame::new(width, height);
+    render_scene_into(&mut frame, models, options);
+    frame
+}
+
+///Renders a scene like render_scene, but into an existing frame buffer, which is cleared first and keeps its size. Drawing every frame of an animation into the same buffer saves allocating a new one each time.
+pub fn render_scene_into(frame: &mut Frame, models: &[Model], options: &RenderOptions) {
+    let (width, height) = (frame.width, frame.height);
+    frame.set_background(options.background);
+    frame.clear();
+    frame.set_depth_test(options.depth_test);
+    let ProjectedScene { projection, world_pos, view_pos, screen_pos, visible } = project_scene(models, options, width, height);
+    let face_color = |face_index: usize| options.colors.map(|colors| colors[face_index % colors.len()]);
+    let default_lights = [Light::new(DEFAULT_LIGHT)];
+    let lights = if options.lights.is_empty() { &default_lights[..] } else { &options.lights };
+
+    // The ground is filled first, and then the shadow of every face of every model is filled on top of it, flattened onto the ground by the shadow matrix of the first light. Everything else is drawn over them, since the models are above the ground.
+    if options.shadow {
+        let to_screen = |v: &Vector| project(&projection, &(options.view * *v)).map(|ndc| viewport(&ndc, width, height));
+        let ground = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]].map(|[x, z]| to_screen(&Vector([x * GROUND_SIZE, GROUND_HEIGHT, z * GROUND_SIZE, 1.0])));
+        if let [Some(p0), Some(p1), Some(p2), Some(p3)] = ground {
+            fill_triangle(frame, p0, p1, p2, GROUND, None);
+            fill_triangle(frame, p0, p2, p3, GROUND, None);
+        }
+        let light = lights[0].direction;
+        if light.0[1] > EPSILON {
+            let shadow = shadow_matrix(&light, GROUND_HEIGHT + SHADOW_OFFSET);
+            for (model, world_pos) in models.iter().zip(&world_pos) {
+                let shadow_pos: Vec<Vector> = world_pos.iter().map(|v| shadow * *v).collect();
+                for face in &model.mesh.faces {
+                    // The shadow is cut off where the ground ends, so it does not hang in the air beyond it.
+                    let outline: Vec<Option<[f32; 3]>> = clip_to_ground(face.iter().map(|&i| shadow_pos[i]).collect()).iter().map(to_screen).collect();
+                    for corners in outline[1.min(outline.len())..].windows(2) {
+                        if let [Some(p0), Some(p1), Some(p2)] = [outline[0], corners[0], corners[1]] {
+                            fill_triangle(frame, p0, p1, p2, SHADOW, None);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // In the filled and shaded modes, each visible face is clipped to the near and far planes and split into triangles fanning out from its first corner and they are all filled, when shaded with a character picked by shade_glyph for its lighting from the face's world-space normal. The faces of all the models are filled together from back to front as seen from the camera, so nearer ones are drawn over farther ones even without the depth buffer.
+    match options.mode {
+        RenderMode::Filled | RenderMode::Shaded | RenderMode::Phong | RenderMode::Normals => {
+            // The camera's position and backward direction in world coordinates, which the view direction of each face for phong comes from
+            let camera = inverse(&options.view).unwrap_or(IDENTITY);
+            let eye = camera * Vector([0.0, 0.0, 0.0, 1.0]);
+            let backward = camera * Vector([0.0, 0.0, 1.0, 0.0]);
+            for (model_index, face_index) in back_to_front(models, &view_pos) {
+                let face = &models[model_index].mesh.faces[face_index];
+                if !visible[model_index][face_index] {
+                    continue;
+                }
+                // The face is cut off at the near and far planes before it is projected, so that a face reaching past the camera is still drawn where it is in view.
+                let outline: Vec<[f32; 3]> = clip_polygon_depth(face.iter().map(|&i| view_pos[model_index][i]).collect(), options.near, options.far)
+                    .iter()
+                    .filter_map(|v| project(&projection, v).map(|ndc| viewport(&ndc, width, height)))
+                    .collect();
+                let glyph = match options.mode {
+                    RenderMode::Shaded => shade_glyph(diffuse_lighting(&face_normal(face, &world_pos[model_index]), lights)),
+                    RenderMode::Phong => {
+                        // With perspective, the face is seen from the direction of the camera, and with an orthographic projection every face is seen from straight behind the camera.
+                        let view = match options.projection {
+                            Projection::Perspective => sub(&eye, &centroid(face, &world_pos[model_index])),
+                            Projection::Orthographic => backward,
+                        };
+                        shade_glyph(phong_lighting(&face_normal(face, &world_pos[model_index]), lights, &view, options.shininess))
+                    }
+                    _ => options.charset.fill,
+                };
+                let rgb = (options.mode == RenderMode::Normals).then(|| normal_color(&face_normal(face, &models[model_index].mesh.vertices)));
+                for corners in outline[1.min(outline.len())..].windows(2) {
+                    let [p0, p1, p2] = [outline[0], corners[0], corners[1]];
+                    match rgb {
+                        Some(rgb) => fill_triangle_rgb(frame, p0, p1, p2, glyph, rgb),
+                        None => fill_triangle(frame, p0, p1, p2, glyph, face_color(face_index)),
+                    }
+                }
+            }
+        }
+        RenderMode::Wireframe => {
+            // The wireframe is drawn with the draw_line function. Each edge is drawn once, even though it borders two faces, if at least one of those faces is visible, in the color of the first visible one. Edges are clipped to the near and far planes in camera coordinates before they are projected, so that an edge passing behind the camera is cut off where it leaves the view instead of coming out mirrored.
+            // With depth cueing, the range of depths of the vertices on the screen goes from the densest character to the lightest.
+            let depth_cue = options.depth_cue.then(|| {
+                let depths = screen_pos.iter().flatten().flatten().map(|&[_, _, z]| z);
+                depths.fold([f32::INFINITY, f32::NEG_INFINITY], |[near, far], z| [near.min(z), far.max(z)])
+            });
+            // Antialiased edges add up how much they cover each cell, and only once they all have is a character picked for it, so that where edges meet or cross the cells are as dense as they should be.
+            let mut coverage = options.antialias.then(|| Coverage::new(width, height));
+            // The edges are only broken once they have all been drawn, when the depth buffer holds the nearest edge in every cell.
+            let mut segments = Vec::new();
+            for (model_index, model) in models.iter().enumerate() {
+                for edge in unique_edges(&model.mesh.faces) {
+                    if let Some(&face_index) = edge.faces.iter().find(|&&face_index| visible[model_index][face_index]) {
+                        let [start, end] = edge.vertices.map(|i| view_pos[model_index][i]);
+                        match &mut coverage {
+                            Some(coverage) => {
+                                if let Some((start, end)) = project_segment(&projection, &start, &end, options, width, height) {
+                                    draw_antialiased_line(coverage, start, end, face_color(face_index));
+                                }
+                            }
+                            None => segments.extend(draw_segment(frame, &projection, &start, &end, options, depth_cue, face_color(face_index))),
+                        }
+                    }
+                }
+            }
+            if let Some(coverage) = coverage {
+                coverage.resolve(frame);
+            }
+            if options.halo {
+                for &(start, end) in &segments {
+                    break_hidden_line(frame, start, end);
+                }
+            }
+        }
+        RenderMode::Points => {
+            // Only the vertices of the visible faces are drawn, each in the color of the first visible face it belongs to.
+            for (model_index, model) in models.iter().enumerate() {
+                let mut vertex_color: Vec<Option<Option<u8>>> = vec![None; model.mesh.vertices.len()];
+                for (face_index, face) in model.mesh.faces.iter().enumerate() {
+                    if visible[model_index][face_index] {
+                        for &i in face {
+                            vertex_color[i].get_or_insert(face_color(face_index));
+                        }
+                    }
+                }
+                for (p, color) in screen_pos[model_index].iter().zip(vertex_color) {
+                    if let (Some([x, y, z]), Some(color)) = (*p, color) {
+                        if let Some([x, y]) = screen_cell(x, y).filter(|_| (-1.0..=1.0).contains(&z)) {
+                            frame.plot(x, y, z, options.charset.vertex, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Markers go just in front of the vertices, so that they are drawn over the ends of the edges. Labels are written over everything, so that the vertices can be told apart even where edges cross them, but only if all of the label fits on the screen.
+    for screen_pos in &screen_pos {
+        for (index, &p) in screen_pos.iter().enumerate() {
+            let Some([x, y, z]) = p else {
+                continue;
+            };
+            let Some([x, y]) = screen_cell(x, y) else {
+                continue;
+            };
+            // Like the points drawn in points mode, markers are only drawn for vertices between the near and far planes, where their edges are.
+            if options.vertex_markers && (-1.0..=1.0).contains(&z) {
+                frame.plot(x, y, z - EPSILON, options.charset.vertex, None);
+            }
+            let label = index.to_string();
+            if options.vertex_labels && y < height && x + label.len() < width {
+                frame.write_text(x + 1, y, &label);
+            }
+        }
+    }
+
+    // The axes are drawn the same way as the edges, each with its name at its tip.
+    if options.axes {
+        for model in models {
+            let model_to_view = options.view * model.model_to_world;
+            let origin = model_to_view * Vector([0.0, 0.0, 0.0, 1.0]);
+            for (axis, (&color, name)) in AXIS_COLORS.iter().zip("xyz".chars()).enumerate() {
+                let mut tip = [0.0, 0.0, 0.0, 1.0];
+                tip[axis] = AXIS_LENGTH;
+                let tip = model_to_view * Vector(tip);
+                draw_segment(frame, &projection, &origin, &tip, options, None, Some(color));
+                if let Some([x, y, z]) = project(&projection, &tip).map(|ndc| viewport(&ndc, width, height)) {
+                    // The name is put just in front of the end of the line, so that it is drawn over it.
+                    if let Some([x, y]) = screen_cell(x, y) {
+                        frame.plot(x, y, z - EPSILON, name, Some(color));
+                    }
+                }
+            }
+        }
+    }
+}
+
+///Renders a scene like render_scene_into, but split into four quadrants like a blueprint: the top view in the top left, the view of the options in the top right, and the front and right side views below them. The front, top and side views are orthographic projections seen from the directions in VIEWS, so that they show the true sizes and angles of the shape.
+pub fn render_views_into(frame: &mut Frame, models: &[Model], options: &RenderOptions) {
+    let (width, height) = (frame.width / 2, frame.height / 2);
+    frame.set_background(options.background);
+    frame.clear();
+    let mut quadrant = Frame::new(width, height);
+    let origin = Vector([0.0, 0.0, 0.0, 1.0]);
+    let [front, top, side] = VIEWS.map(|(direction, up)| RenderOptions {
+        projection: Projection::Orthographic,
+        view: look_at(&add(&origin, &scale_vec(&direction, VIEW_DISTANCE)), &origin, &up),
+        ..options.clone()
+    });
+    for (options, [x, y]) in [(&top, [0, 0]), (options, [width, 0]), (&front, [0, height]), (&side, [width, height])] {
+        render_scene_into(&mut quadrant, models, options);
+        frame.blit(x, y, &quadrant);
+    }
+}
+
+///Renders a scene like render_scene_into, but as a red and cyan anaglyph to be seen through glasses with a red filter over the left eye and a cyan one over the right. The scene is drawn once from each eye, which are separation apart across the view, the left in ANAGLYPH_LEFT and the right in ANAGLYPH_RIGHT, and the two are overlaid in the same frame, with ANAGLYPH_BOTH where they overlap.
+pub fn render_anaglyph_into(frame: &mut Frame, models: &[Model], options: &RenderOptions, separation: f32) {
+    // Moving an eye to one side is the same as moving the world the other way in camera coordinates. The view from each eye is all in its own color, so faces that would be colored by their normals are just filled.
+    let eye = |offset: f32, color: u8| RenderOptions {
+        view: translation(-offset, 0.0, 0.0) * options.view,
+        colors: Some([color; 6]),
+        mode: if options.mode == RenderMode::Normals { RenderMode::Filled } else { options.mode },
+        ..options.clone()
+    };
+    render_scene_into(frame, models, &eye(-separation / 2.0, ANAGLYPH_LEFT));
+    let mut right = Frame::new(frame.width, frame.height);
+    render_scene_into(&mut right, models, &eye(separation / 2.0, ANAGLYPH_RIGHT));
+    for i in 0..frame.chars.len() {
+        if right.chars[i] == right.background {
+            continue;
+        }
+        // Where both eyes see something, the nearer of the two is drawn.
+        let both = frame.chars[i] != frame.background;
+        if !both || right.depth[i] < frame.depth[i] {
+            frame.chars[i] = right.chars[i];
+            frame.depth[i] = right.depth[i];
+        }
+        frame.colors[i] = if both { Some(ANAGLYPH_BOTH) } else { right.colors[i] };
+    }
+}
+
+/// Finds the cell a point on the screen is in, which is the one whose center is nearest, or None if it is left of or above the screen. Casting a coordinate straight to usize would truncate it, moving everything up and to the left by up to a cell, and would turn a small negative coordinate into cell 0.
+pub fn screen_cell(x: f32, y: f32) -> Option<[usize; 2]> {
+    let [x, y] = [x.round(), y.round()];
+    (x >= 0.0 && y >= 0.0).then_some([x as usize, y as usize])
+}
+
+/// Colors of the x, y and z axes drawn by render_scene: red, green and blue
+const AXIS_COLORS : [u8; 3] = [196, 46, 21];
+
+/// Length of the axes drawn by render_scene, in the units of the model, so that they reach a little way out of the cube
+const AXIS_LENGTH : f32 = 1.5;
+
+/// Draws the line between two points in camera coordinates into a frame, after clipping it to the near and far planes of options and projecting it onto the screen, and returns the screen coordinates and depths of the ends of what was drawn. If a depth range is given, the line is depth-cued within it.
+fn draw_segment(frame: &mut Frame, projection: &Matrix, start: &Vector, end: &Vector, options: &RenderOptions, depth_cue: Option<[f32; 2]>, color: Option<u8>) -> Option<([f32; 3], [f32; 3])> {
+    let (start, end) = project_segment(projection, start, end, options, frame.width, frame.height)?;
+    match depth_cue {
+        Some(range) => draw_depth_cued_line(frame, start, end, range, color),
+        None => draw_line(frame, start, end, &options.charset, color),
+    }
+    Some((start, end))
+}
+
+/// Clips the line between two points in camera coordinates to the near and far planes of options and projects what is left onto a screen of the given size, returning the screen coordinates and depths of its ends
+pub(crate) fn project_segment(projection: &Matrix, start: &Vector, end: &Vector, options: &RenderOptions, width: usize, height: usize) -> Option<([f32; 3], [f32; 3])> {
+    let (start, end) = clip_depth(start, end, options.near, options.far)?;
+    let [start, end] = [start, end].map(|v| project(projection, &v).map(|ndc| viewport(&ndc, width, height)));
+    Some((start?, end?))
+}
+
+/// Computes the outward unit normal of a face from the positions of its vertices. It is found with Newell's method, which adds up the area the face covers seen along each axis, so that it takes every vertex into account and points the right way even for faces that are concave or not quite flat, where the first three vertices can turn the wrong way. Faces are wound clockwise when seen from outside, which is the opposite of the usual winding for Newell's method, so the sum is negated.
+pub fn face_normal(face: &[usize], vertices: &[Vector]) -> Vector {
+    let mut normal = [0.0; 3];
+    for (i, &index) in face.iter().enumerate() {
+        let [x0, y0, z0, _] = vertices[index].0;
+        let [x1, y1, z1, _] = vertices[face[(i + 1) % face.len()]].0;
+        normal[0] -= (y0 - y1) * (z0 + z1);
+        normal[1] -= (z0 - z1) * (x0 + x1);
+        normal[2] -= (x0 - x1) * (y0 + y1);
+    }
+    normalize(&Vector([normal[0], normal[1], normal[2], 0.0]))
+}
+
+/// Whether a face with the given outward normal, and with its centroid at the given point, both in camera coordinates, faces away from the viewer. With perspective, that is when the normal points along the line of sight from the camera to the face, and with an orthographic projection, when it points away from the camera down the -z axis. Faces seen exactly edge on count as facing away.
+pub fn is_back_face(normal: &Vector, centroid: &Vector, projection: Projection) -> bool {
+    match projection {
+        Projection::Perspective => dot(normal, centroid) >= 0.0,
+        Projection::Orthographic => normal.0[2] <= 0.0,
+    }
+}
+
+/// Orders the faces of all the models from back to front, for drawing with the painter's algorithm, given the camera-space positions of the vertices of each model. Each face is listed as the index of its model and its index in the model's mesh. Faces are compared by the depth of their centroid; the camera looks down the -z axis, so the farthest face has the lowest z.
+pub fn back_to_front(models: &[Model], view_pos: &[Vec<Vector>]) -> Vec<(usize, usize)> {
+    let mut order: Vec<(usize, usize)> = Vec::new();
+    for (model_index, model) in models.iter().enumerate() {
+        order.extend((0..model.mesh.faces.len()).map(|face_index| (model_index, face_index)));
+    }
+    let depth = |&(model_index, face_index): &(usize, usize)| centroid_z(&models[model_index].mesh.faces[face_index], &view_pos[model_index]);
+    order.sort_by(|a, b| depth(a).total_cmp(&depth(b)));
+    order
+}
+
+/// Average position of the vertices of a face
+fn centroid(face: &[usize], vertices: &[Vector]) -> Vector {
+    let sum = face.iter().fold(Vector([0.0; 4]), |sum, &i| add(&sum, &vertices[i]));
+    Vector(sum.0.map(|c| c / face.len() as f32))
+}
+
+/// Depth of the centroid of a face, given the positions of its vertices
+fn centroid_z(face: &[usize], positions: &[Vector]) -> f32 {
+    face.iter().map(|&i| positions[i].0[2]).sum::<f32>() / face.len() as f32
+}
+
+/// An edge between two vertices of a shape, along with the faces it borders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    /// Indices of the vertices at the ends of the edge, the smaller one first
+    pub vertices: [usize; 2],
+    /// Indices of the faces that border the edge, in the order they are listed
+    pub faces: Vec<usize>,
+}
+
+/// Lists every edge of a shape once, given its faces. An edge shared by two faces appears only once, tagged with both.
+pub fn unique_edges(faces: &[Vec<usize>]) -> Vec<Edge> {
+    let mut edges: Vec<Edge> = Vec::new();
+    // Where each edge is in the list, so that finding it again does not take longer the more edges there are
+    let mut index: std::collections::HashMap<[usize; 2], usize> = std::collections::HashMap::new();
+    for (face_index, face) in faces.iter().enumerate() {
+        let mut end = face[face.len() - 1];
+        for &start in face {
+            let vertices = [start.min(end), start.max(end)];
+            match index.get(&vertices) {
+                Some(&i) => edges[i].faces.push(face_index),
+                None => {
+                    index.insert(vertices, edges.len());
+                    edges.push(Edge { vertices, faces: vec![face_index] });
+                }
+            }
+            end = start;
+        }
+    }
+    edges
+}
+
+///Determines whether a triangle formed by three 2D coordinates should be drawn by calculating the cross product of the edges of the triangle. If the cross product is negative, the triangle is culled (not drawn). If the cross product is positive, the triangle is not culled (drawn). The renderer no longer uses it, since the winding of the first three vertices of a face on the screen can be the wrong way round for concave or bent faces.
+#[deprecated(note = "use is_back_face with the face_normal of the whole face instead")]
+pub fn cull(p0: [f32; 2], p1: [f32; 2], p2: [f32; 2]) -> bool {
+    let dx = [p1[0] - p0[0], p2[0] - p1[0]];
+    let dy = [p1[1] - p0[1], p2[1] - p1[1]];
+    dx[0] * dy[1] < dx[1] * dy[0]
+}
+
+///The draw_line function draws a line between two screen coordinates into a frame. The third coordinate of each endpoint is its depth, which is interpolated along the line; a cell is only overwritten if the line is nearer than what was drawn there before, so nearer lines hide farther ones. Both endpoints are rounded to the nearest cell and the cells in between are found with Bresenham's integer algorithm, so every cell of the line is touched exactly once and there are no gaps. Each line is drawn in the given color with the character of the charset that line_glyph picks for its slope. The line is clipped to the screen with clip_line first, so parts that leave the screen are not drawn.
+pub fn draw_line(frame: &mut Frame, start: [f32; 3], end: [f32; 3], charset: &Charset, color: Option<u8>) {
+    let glyph = line_glyph(end[0] - start[0], end[1] - start[1], charset);
+    rasterize_line(frame, start, end, color, |_| glyph);
+}
+
+/// Characters for depth-cued lines, from the farthest to the nearest
+pub const DEPTH_CUE : &[u8] = b".:-=+*#";
+
+/// Draws a line like draw_line, but picks the character for each cell from DEPTH_CUE by the depth of the line there, going from '.' at the far end of the depth range to '#' at the near end
+pub fn draw_depth_cued_line(frame: &mut Frame, start: [f32; 3], end: [f32; 3], [near, far]: [f32; 2], color: Option<u8>) {
+    rasterize_line(frame, start, end, color, |z| {
+        let nearness = if far > near { ((far - z) / (far - near)).clamp(0.0, 1.0) } else { 1.0 };
+        DEPTH_CUE[(nearness * (DEPTH_CUE.len() - 1) as f32).round() as usize] as char
+    });
+}
+
+/// Characters for antialiased lines, from a cell the line does not cover at all to one it covers completely
+pub const ANTIALIAS_SHADES : &[u8] = b" .:-=+*#";
+
+/// How much of each cell of a frame is covered by antialiased lines. The coverage of all the lines is added up before resolve picks the character for each cell, so that cells that two lines pass through are drawn denser.
+pub struct Coverage {
+    width: usize,
+    height: usize,
+    /// The coverage of each cell, where 1 is fully covered
+    amounts: Vec<f32>,
+    /// The depth of the nearest line through each cell
+    depth: Vec<f32>,
+    /// The color of the nearest line through each cell
+    colors: Vec<Option<u8>>,
+}
+
+impl Coverage {
+    /// Creates the coverage of a frame of the given size with nothing drawn yet
+    pub fn new(width: usize, height: usize) -> Self {
+        let cells = width * height;
+        Coverage {
+            width,
+            height,
+            amounts: vec![0.0; cells],
+            depth: vec![f32::INFINITY; cells],
+            colors: vec![None; cells],
+        }
+    }
+
+    /// How much of the cell at (x, y) is covered
+    pub fn get(&self, x: usize, y: usize) -> f32 {
+        self.amounts[y * self.width + x]
+    }
+
+    /// Adds to the coverage of a cell by a line at depth z, ignoring cells off the frame
+    fn add(&mut self, x: i32, y: i32, amount: f32, z: f32, color: Option<u8>) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        let index = y as usize * self.width + x as usize;
+        self.amounts[index] += amount;
+        if z < self.depth[index] {
+            self.depth[index] = z;
+            self.colors[index] = color;
+        }
+    }
+
+    /// Draws every covered cell into a frame with the character from ANTIALIAS_SHADES for its coverage, at the depth of the nearest line through it
+    pub fn resolve(&self, frame: &mut Frame) {
+        for y in 0..self.height.min(frame.height) {
+            for x in 0..self.width.min(frame.width) {
+                let index = y * self.width + x;
+                let shade = (self.amounts[index].min(1.0) * (ANTIALIAS_SHADES.len() - 1) as f32).round() as usize;
+                if shade > 0 {
+                    frame.plot(x, y, self.depth[index], ANTIALIAS_SHADES[shade] as char, self.colors[index]);
+                }
+            }
+        }
+    }
+}
+
+/// Adds a line between two screen coordinates to the coverage of a frame with Xiaolin Wu's algorithm. At each step along the longer axis of the line it covers the two cells either side of where the line passes, each by how close the line is to its center, so the line fades from one row or column into the next instead of jumping. The third coordinate of each endpoint is its depth.
+pub fn draw_antialiased_line(coverage: &mut Coverage, start: [f32; 3], end: [f32; 3], color: Option<u8>) {
+    let Some((start, end)) = clip_line(start, end, coverage.width, coverage.height) else {
+        return;
+    };
+    // A steep line is drawn as a shallow one with x and y swapped, and swapped back as each cell is covered.
+    let steep = (end[1] - start[1]).abs() > (end[0] - start[0]).abs();
+    let swap = |[x, y, z]: [f32; 3]| if steep { [y, x, z] } else { [x, y, z] };
+    let (mut a, mut b) = (swap(start), swap(end));
+    if a[0] > b[0] {
+        std::mem::swap(&mut a, &mut b);
+    }
+    let gradient = if b[0] > a[0] { (b[1] - a[1]) / (b[0] - a[0]) } else { 0.0 };
+    let [first, last] = [a[0], b[0]].map(|c| c.round() as i32);
+    for step in first..=last {
+        let t = if last > first { (step - first) as f32 / (last - first) as f32 } else { 0.0 };
+        let z = a[2] + (b[2] - a[2]) * t;
+        let across = a[1] + gradient * (step as f32 - a[0]);
+        let (cell, fraction) = (across.floor(), across - across.floor());
+        for (cell, amount) in [(cell as i32, 1.0 - fraction), (cell as i32 + 1, fraction)] {
+            let (x, y) = if steep { (cell, step) } else { (step, cell) };
+            coverage.add(x, y, amount, z, color);
+        }
+    }
+}
+
+/// Draws the cells of a line for draw_line and draw_depth_cued_line, with the character glyph gives for the depth of each cell
+fn rasterize_line(frame: &mut Frame, start: [f32; 3], end: [f32; 3], color: Option<u8>, glyph: impl Fn(f32) -> char) {
+    // Cells that fall outside the frame are skipped so off-screen lines are truncated.
+    for (x, y, z) in line_cells(start, end, frame.width, frame.height) {
+        frame.plot(x as usize, y as usize, z, glyph(z), color);
+    }
+}
+
+/// How many cells of an edge are left out either side of where it passes behind a nearer one with RenderOptions::halo
+pub const HALO_WIDTH : usize = 1;
+
+/// How much nearer than an edge another one has to be to break it with RenderOptions::halo. Edges that meet at a vertex are at almost the same depth close to it, so they do not break each other there.
+const HALO_DEPTH : f32 = 0.01;
+
+/// Breaks a line that has been drawn into a frame by draw_line or draw_depth_cued_line wherever it passes behind something nearer, by emptying HALO_WIDTH of its cells either side of each cell in which it is hidden. Only cells the line itself still shows are emptied, so nothing nearer is touched.
+fn break_hidden_line(frame: &mut Frame, start: [f32; 3], end: [f32; 3]) {
+    let cells = line_cells(start, end, frame.width, frame.height);
+    let index = |x: i32, y: i32| (x >= 0 && y >= 0 && (x as usize) < frame.width && (y as usize) < frame.height).then(|| y as usize * frame.width + x as usize);
+    let hidden: Vec<bool> = cells.iter().map(|&(x, y, z)| index(x, y).is_some_and(|i| frame.depth[i] < z - HALO_DEPTH)).collect();
+    for (i, &(x, y, z)) in cells.iter().enumerate() {
+        let near_hidden = hidden[i.saturating_sub(HALO_WIDTH)..(i + HALO_WIDTH + 1).min(cells.len())].iter().any(|&hidden| hidden);
+        if let Some(cell) = index(x, y).filter(|&cell| near_hidden && frame.depth[cell] == z) {
+            frame.chars[cell] = frame.background;
+            frame.depth[cell] = f32::INFINITY;
+            frame.colors[cell] = None;
+            frame.rgb[cell] = None;
+        }
+    }
+}
+
+/// The cells of a line between two screen coordinates, with the depth of the line in each, after clipping it to a screen of the given size. Both endpoints are rounded to the nearest cell and the cells in between are found with Bresenham's integer algorithm. Clipping can leave the rounded ends just off the screen, so cells may still fall outside it.
+fn line_cells(start: [f32; 3], end: [f32; 3], width: usize, height: usize) -> Vec<(i32, i32, f32)> {
+    let mut cells = Vec::new();
+    let Some((start, end)) = clip_line(start, end, width, height) else {
+        return cells;
+    };
+    let [x0, y0] = [start[0], start[1]].map(|c| c.round() as i32);
+    let [x1, y1] = [end[0], end[1]].map(|c| c.round() as i32);
+    let (z0, z1) = (start[2], end[2]);
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    // Depth changes by the same amount for each cell, since each step moves along the longer axis.
+    let steps = dx.max(-dy).max(1);
+    let dz = (z1 - z0) / steps as f32;
+    // Accumulated error between the ideal line and the cells plotted so far.
+    let mut err = dx + dy;
+    let [mut x, mut y] = [x0, y0];
+    let mut z = z0;
+    loop {
+        cells.push((x, y, z));
+        if x == x1 && y == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+        z += dz;
+    }
+    cells
+}
+
+/// Picks the character of a charset that looks most like a line going dx cells across and dy cells down: the horizontal or vertical one, the falling one for a line going down to the right or the rising one for a line going down to the left. Cells are taken to be twice as tall as they are wide, so a line going two cells across for every cell down looks diagonal.
+pub fn line_glyph(dx: f32, dy: f32, charset: &Charset) -> char {
+    // Slope of the line as it looks on the screen, compared with those of lines at 22.5 and 67.5 degrees, halfway between the directions of the characters.
+    let slope = 2.0 * dy.abs() / dx.abs();
+    if slope < std::f32::consts::SQRT_2 - 1.0 || slope.is_nan() {
+        charset.horizontal
+    } else if slope > std::f32::consts::SQRT_2 + 1.0 {
+        charset.vertical
+    } else if (dx > 0.0) == (dy > 0.0) {
+        charset.falling
+    } else {
+        charset.rising
+    }
+}
+
+///Fills the triangle between three screen coordinates with a character using a scanline rasterizer. For every row of the screen inside the triangle, the points where the row crosses the edges of the triangle give the span of cells to fill, and the depth is interpolated along the edges and then along the span. A cell is covered if its center is inside the triangle, and like draw_line it is drawn in the given color and only overwritten if the triangle is nearer than what was drawn there before.
+pub fn fill_triangle(frame: &mut Frame, p0: [f32; 3], p1: [f32; 3], p2: [f32; 3], glyph: char, color: Option<u8>) {
+    rasterize_triangle(frame, p0, p1, p2, |frame, x, y, z| frame.plot(x, y, z, glyph, color));
+}
+
+/// Fills a triangle like fill_triangle, but in a 24-bit color
+pub fn fill_triangle_rgb(frame: &mut Frame, p0: [f32; 3], p1: [f32; 3], p2: [f32; 3], glyph: char, rgb: [u8; 3]) {
+    rasterize_triangle(frame, p0, p1, p2, |frame, x, y, z| frame.plot_rgb(x, y, z, glyph, rgb));
+}
+
+/// Finds the cells of a triangle for fill_triangle and fill_triangle_rgb, and draws each into the frame with plot at its depth
+fn rasterize_triangle(frame: &mut Frame, p0: [f32; 3], p1: [f32; 3], p2: [f32; 3], mut plot: impl FnMut(&mut Frame, usize, usize, f32)) {
+    let ymin = p0[1].min(p1[1]).min(p2[1]).ceil().max(0.0) as usize;
+    let ymax = p0[1].max(p1[1]).max(p2[1]).floor().min(frame.height as f32 - 1.0);
+    if ymax < 0.0 {
+        return;
+    }
+    for y in ymin..=ymax as usize {
+        let fy = y as f32;
+        // The span starts and ends where the row crosses the edges, as (x, depth) pairs.
+        let mut span: Option<([f32; 2], [f32; 2])> = None;
+        for (a, b) in [(p0, p1), (p1, p2), (p2, p0)] {
+            if a[1] == b[1] || fy < a[1].min(b[1]) || fy > a[1].max(b[1]) {
+                continue;
+            }
+            let t = (fy - a[1]) / (b[1] - a[1]);
+            let crossing = [a[0] + (b[0] - a[0]) * t, a[2] + (b[2] - a[2]) * t];
+            span = Some(match span {
+                None => (crossing, crossing),
+                Some((left, right)) => (
+                    if crossing[0] < left[0] { crossing } else { left },
+                    if crossing[0] > right[0] { crossing } else { right },
+                ),
+            });
+        }
+        let Some(([xl, zl], [xr, zr])) = span else {
+            continue;
+        };
+        let xmin = xl.ceil().max(0.0) as usize;
+        let xmax = xr.floor().min(frame.width as f32 - 1.0);
+        if xmax < 0.0 {
+            continue;
+        }
+        let dzdx = if xr > xl { (zr - zl) / (xr - xl) } else { 0.0 };
+        for x in xmin..=xmax as usize {
+            let z = zl + (x as f32 - xl) * dzdx;
+            plot(frame, x, y, z);
+        }
+    }
+}
+
+// Outcode bits used by clip_line.
+const INSIDE : u8 = 0;
+const LEFT : u8 = 1;
+const RIGHT : u8 = 2;
+const BOTTOM : u8 = 4;
+const TOP : u8 = 8;
+
+/// Computes the Cohen–Sutherland outcode of a point, telling which sides of the rectangle from (xmin, ymin) to (xmax, ymax) it lies beyond
+fn outcode(p: [f32; 3], [xmin, ymin]: [f32; 2], [xmax, ymax]: [f32; 2]) -> u8 {
+    let [x, y, _] = p;
+    let mut code = INSIDE;
+    if x < xmin {
+        code |= LEFT;
+    } else if x > xmax {
+        code |= RIGHT;
+    }
+    if y < ymin {
+        code |= TOP;
+    } else if y > ymax {
+        code |= BOTTOM;
+    }
+    code
+}
+
+///Clips the line between two screen coordinates to the rectangle of a screen of the given size using the Cohen–Sutherland algorithm. Endpoints lying outside the screen are moved along the line onto the edge they cross, so the slope of the line is kept and the depth is interpolated to the new endpoint. Returns None if no part of the line is on the screen.
+pub fn clip_line(start: [f32; 3], end: [f32; 3], width: usize, height: usize) -> Option<([f32; 3], [f32; 3])> {
+    // Points are rounded to the nearest cell, so the screen extends half a cell beyond the centers of the cells on its border.
+    let [xmin, ymin] = [-0.5, -0.5];
+    let [xmax, ymax] = [width as f32 - 0.5, height as f32 - 0.5];
+    let [mut p0, mut p1] = [start, end];
+    let [mut code0, mut code1] = [outcode(p0, [xmin, ymin], [xmax, ymax]), outcode(p1, [xmin, ymin], [xmax, ymax])];
+    loop {
+        if code0 | code1 == INSIDE {
+            return Some((p0, p1));
+        }
+        if code0 & code1 != INSIDE {
+            // Both endpoints are beyond the same edge.
+            return None;
+        }
+        // Move an endpoint that is outside onto the edge it lies beyond.
+        let code = if code0 != INSIDE { code0 } else { code1 };
+        let [x0, y0, z0] = p0;
+        let [x1, y1, z1] = p1;
+        let p = if code & TOP != 0 {
+            let t = (ymin - y0) / (y1 - y0);
+            [x0 + (x1 - x0) * t, ymin, z0 + (z1 - z0) * t]
+        } else if code & BOTTOM != 0 {
+            let t = (ymax - y0) / (y1 - y0);
+            [x0 + (x1 - x0) * t, ymax, z0 + (z1 - z0) * t]
+        } else if code & LEFT != 0 {
+            let t = (xmin - x0) / (x1 - x0);
+            [xmin, y0 + (y1 - y0) * t, z0 + (z1 - z0) * t]
+        } else {
+            let t = (xmax - x0) / (x1 - x0);
+            [xmax, y0 + (y1 - y0) * t, z0 + (z1 - z0) * t]
+        };
+        if code == code0 {
+            p0 = p;
+            code0 = outcode(p0, [xmin, ymin], [xmax, ymax]);
+        } else {
+            p1 = p;
+            code1 = outcode(p1, [xmin, ymin], [xmax, ymax]);
+        }
+    }
+}
+
+/// Clips the line between two points in camera coordinates to the part between the near and far planes, at distances near and far in front of the camera along the -z axis. Endpoints beyond a plane are moved along the line onto it. Returns None if no part of the line is between the planes.
+pub fn clip_depth(start: &Vector, end: &Vector, near: f32, far: f32) -> Option<(Vector, Vector)> {
+    let (z0, z1) = (start.0[2], end.0[2]);
+    let [mut t0, mut t1] = [0.0f32, 1.0];
+    // How far each endpoint is beyond the near and the far plane, which is positive on the side that is clipped away.
+    for (d0, d1) in [(z0 + near, z1 + near), (-far - z0, -far - z1)] {
+        if d0 > 0.0 && d1 > 0.0 {
+            return None;
+        }
+        if d0 > 0.0 {
+            t0 = t0.max(d0 / (d0 - d1));
+        } else if d1 > 0.0 {
+            t1 = t1.min(d0 / (d0 - d1));
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    let direction = sub(end, start);
+    Some((add(start, &scale_vec(&direction, t0)), add(start, &scale_vec(&direction, t1))))
+}
+
+/// Clips a polygon in camera coordinates to the part between the near and far planes like clip_depth does for a line, keeping its corners in order. Where an edge of the polygon crosses a plane a corner is added on the plane, and the corners beyond it are left out. Returns no corners if no part of the polygon is between the planes.
+pub fn clip_polygon_depth(mut polygon: Vec<Vector>, near: f32, far: f32) -> Vec<Vector> {
+    // The polygon is clipped to each plane in turn, by how far each corner is beyond it, which is positive on the side that is clipped away.
+    for (sign, offset) in [(1.0, near), (-1.0, -far)] {
+        let beyond = |v: &Vector| sign * v.0[2] + offset;
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+        for (i, current) in polygon.iter().enumerate() {
+            let previous = &polygon[(i + polygon.len() - 1) % polygon.len()];
+            let (d0, d1) = (beyond(previous), beyond(current));
+            if (d0 > 0.0) != (d1 > 0.0) {
+                clipped.push(add(previous, &scale_vec(&sub(current, previous), d0 / (d0 - d1))));
+            }
+            if d1 <= 0.0 {
+                clipped.push(*current);
+            }
+        }
+        polygon = clipped;
+    }
+    polygon
+}
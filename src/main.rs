@@ -1,157 +1,808 @@
 //! A spinning text cube
-//! 
-//! 
-//! 4    +------+  6
-//!     /|     /| 
-//! 5  +------+ |  7
-//!    | |    | | 
-//! 0  | +----|-+  2
-//!    |/     |/   
-//! 1  +------+    3
-
-#[derive(Debug, Clone, Copy)]
-struct Matrix([[f32; 4]; 4]);
-
-#[derive(Debug, Clone, Copy)]
-struct Vector([f32; 4]);
-
-/// Vertices of a cube in 3D space
-const VERTICES : [Vector; 8] = [    Vector([-1.0, -1.0, -1.0, 1.0]),
-    Vector([-1.0, -1.0,  1.0, 1.0]),
-    Vector([ 1.0, -1.0, -1.0, 1.0]),
-    Vector([ 1.0, -1.0,  1.0, 1.0]),
-    Vector([-1.0,  1.0, -1.0, 1.0]),
-    Vector([-1.0,  1.0,  1.0, 1.0]),
-    Vector([ 1.0,  1.0, -1.0, 1.0]),
-    Vector([ 1.0,  1.0,  1.0, 1.0]),
-];
-
-/// Indices of the vertices that make up each face of the cube
-const FACES : [[u8; 4]; 6] = [    [1, 5, 7, 3],
-    [3, 7, 6, 2],
-    [0, 4, 5, 1],
-    [2, 6, 4, 0],
-    [0, 1, 3, 2],
-    [5, 4, 6, 7],
-];
-
-/// Performs a matrix-vector multiplication
-fn matrix_times_vector(m: &Matrix, v: &Vector) -> Vector {
-    let [mx, my, mz, mw] = &m.0;
-    let [x, y, z, w] = v.0;
-    // The product is the weighted sum of the columns.
-    Vector([
-        x * mx[0] + y * my[0] + z * mz[0] + w * mw[0],
-        x * mx[1] + y * my[1] + z * mz[1] + w * mw[1],
-        x * mx[2] + y * my[2] + z * mz[2] + w * mw[2],
-        x * mx[3] + y * my[3] + z * mz[3] + w * mw[3],
-    ])
-}
-
-const SCREEN_WIDTH : usize = 80;
-const SCREEN_HEIGHT : usize = 40;
-
-/// Offset of the screen in the x direction
-const OFFSET_X : f32 = SCREEN_WIDTH as f32 * 0.5;
-
-/// Offset of the screen in the y direction
-const OFFSET_Y : f32 = SCREEN_HEIGHT as f32 * 0.5;
-
-/// Scaling factor for the x direction
-const SCALE_X : f32 = SCREEN_WIDTH as f32 * 0.5;
-
-/// Scaling factor for the y direction
-const SCALE_Y : f32 = SCREEN_HEIGHT as f32 * 0.5;
 
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime};
 
+use simple_rust_cube::controls::{Controls, MAX_DISTANCE, MAX_FOV, MIN_DISTANCE, MIN_FOV};
+use simple_rust_cube::export::{frame_to_ppm, frame_to_svg, save_screenshot, GifExport};
+use simple_rust_cube::input::parse_input;
+use simple_rust_cube::term::{self, AlternateScreen, HiddenCursor, MouseReporting, Output, RawMode};
+use simple_rust_cube::obj::parse_obj;
+use simple_rust_cube::quat::tumble;
+use simple_rust_cube::record::{parse_recording, FrameState};
+use simple_rust_cube::scene::SceneConfig;
+use simple_rust_cube::stats::RenderStats;
+use simple_rust_cube::timing::{angle_at, frame_delay, hud_text};
+use simple_rust_cube::{length, render_anaglyph_into, render_scene_into, render_views_into, screen_fit_scale, Charset, CullMode, Frame, Glyphs, Light, Matrix, Mesh, Model, oscillation, RenderMode, RenderOptions, scale, translation, Vector, FACE_COLORS, IDENTITY, SCREEN_HEIGHT, SCREEN_WIDTH};
 
-fn main() {
-    for frame_number in 0.. {
-        let mut frame = [[b' ';SCREEN_WIDTH]; SCREEN_HEIGHT];
-
-        /// Time elapsed since the beginning of the animation
-        let t = frame_number as f32 * 0.01;
-        let (c, s) = (t.cos(), t.sin());
-
-        /// Transformation matrix that rotates the cube around the y-axis
-        let cube_to_world = Matrix([
-            // Each row is a column of a matrix.
-            [  c, 0.0,   s, 0.0],
-            [0.0, 1.0, 0.0, 0.0],
-            [ -s, 0.0,   c, 0.0],
-            [0.0, 0.0,-2.5, 1.0],
-        ]);
-
-        
-        ///Transforms the 3D positions of the vertices of the cube into 2D screen coordinates by applying a transformation matrix and constants to the 3D positions to obtain the world coordinates and then projecting the world coordinates onto the 2D screen, storing the resulting 2D coordinates in the screen_pos array.
-        let mut screen_pos = [[0.0, 0.0]; 8];
-        for (v, s) in VERTICES.iter().zip(screen_pos.iter_mut()) {
-            let world_pos = matrix_times_vector(&cube_to_world, v);
-            let recip_z = 1.0 /  world_pos.0[2];
-            let screen_x = world_pos.0[0] * recip_z * SCALE_X + OFFSET_X;
-            let screen_y = world_pos.0[1] * recip_z * SCALE_Y + OFFSET_Y;
-            *s = [screen_x, screen_y];
-            // frame[screen_y as usize][screen_x as usize] = b'.';
-        }
-
-
-        ///Iterates over the faces of the cube and, for each face, uses the cull function to determine whether the face should be drawn. If the face should be drawn, it uses the draw_line function to draw lines between the vertices of the face to create a wireframe representation of the face on the screen. The end variable is used to store the last vertex of the face, so that lines can be drawn between consecutive vertices of the face in the correct order.
-        for face in FACES {
-            if !cull(screen_pos[face[0] as usize], screen_pos[face[1] as usize], screen_pos[face[2] as usize]) {
-                let mut end = face[3];
-                for start in face {
-                    draw_line(&mut frame, screen_pos[start as usize], screen_pos[end as usize]);
-                    end = start;
-                }
-            }
+const USAGE : &str = "\
+Usage: simple-rust-cube [OPTIONS]
+
+Options:
+  --width <N>    Width of the screen in characters
+  --height <N>   Height of the screen in characters. Without --width or
+                 --height, the screen fills the terminal, or is 80x40 if the
+                 size of the terminal is unknown, and it is resized with the
+                 terminal unless the animation is being saved as a GIF or
+                 written with --plain. With only one of them, the other is 80
+                 or 40.
+  --speed <X>    Multiplier for the rotation speed, 0 freezes the cube (default 1)
+  --rx <X>, --ry <X>, --rz <X>
+                 Rates in radians per second at which the cube spins around its
+                 x-, y- and z-axes, so that it tumbles if more than one is set
+                 (default 0, 0.33 and 0). Without --rx or --rz, --ry turns it
+                 around the axis of the scene file, if there is one.
+  --motion <M>   How the cube moves: spin round and round, or oscillate,
+                 rocking back and forth (default spin)
+  --amplitude <DEG>
+                 How far the cube rocks either way with oscillate (default 45)
+  --frequency <X>
+                 How fast the cube rocks with oscillate, in radians of its swing
+                 per second (default 1)
+  --fov <DEG>    Vertical field of view in degrees, from 10 to 120 (default 90)
+  --distance <X> How far the camera is from the cube, from 2 to 50 (default
+                 2.5, or as in the scene file)
+  --fps <N>      Frames drawn per second, 0 draws as fast as possible (default 33)
+  --frames <N>   Draw N frames and exit instead of running forever
+  --glyphs <G>   How the cube is drawn: ascii, box, which draws the lines with
+                 box-drawing characters, half-blocks, which has twice the
+                 vertical resolution, or braille, which has twice the
+                 horizontal and four times the vertical resolution
+                 (default ascii)
+  --mode <M>     What is drawn: wireframe, filled faces, shaded faces lit from
+                 the upper left, phong, which shades them with a highlight
+                 where they reflect the light towards you, normals, which fills
+                 them in a 24-bit color for the way they face, for truecolor
+                 terminals, or points at the vertices (default wireframe)
+  --light <X,Y,Z[,I]>
+                 Light the shaded faces from the direction X,Y,Z with the
+                 intensity I (default 1). Give it more than once for more
+                 lights, whose light is added up. Without it, the faces are lit
+                 from the upper left, or as in the scene file.
+  --shininess <X>
+                 How shiny the faces are with phong, where higher values give a
+                 smaller, sharper highlight (default 16)
+  --shape <S>    Shape to spin: cube, tetrahedron, octahedron or icosahedron
+                 (default cube)
+  --depth-cue    Draw nearer parts of edges with denser characters
+  --antialias    Smooth the edges by drawing them with denser characters where
+                 they cover more of a cell, instead of depth cueing them
+  --halo         Also draw the edges at the back, broken where they pass behind
+                 nearer edges, like a hidden-line drawing
+  --views        Split the screen into the top view, the usual view, and the
+                 front and right side views, like a blueprint
+  --anaglyph     Draw the scene for red and cyan 3D glasses, as seen from the
+                 left eye in red and from the right eye in cyan
+  --eye-separation <X>
+                 How far apart the eyes are with --anaglyph (default 0.2)
+  --shadow       Draw the ground below the shape with the shadow the first light
+                 casts onto it
+  --axes         Draw the x, y and z axes of the shape in red, green and blue
+  --colors       Draw each face of the shape in its own color, going through
+                 red, green, blue, yellow, magenta and cyan
+  --charset <C>  Characters to draw with, in the order horizontal, vertical,
+                 rising and falling lines, vertices and filled faces, such as
+                 '─│╱╲*#' (default '-|/\\*#')
+  --background <C>
+                 Character to fill the empty part of the screen with, such as
+                 '.', only with ascii or box glyphs (default ' ')
+  --vertices     Mark the vertices of the shape with the vertex character
+  --labels       Write the index of each vertex next to it
+  --hud          Show the frame rate and frame number in the top-left corner,
+                 only with ascii glyphs
+  --plain        Write each frame below the previous one, with a blank line in
+                 between, and no escape sequences to move the cursor or color
+                 the text, for terminals that do not handle them. This is how
+                 the animation is written when stdout is not a terminal.
+  --stats        When the run ends, print the fewest, most, mean and
+                 percentile times it took to render a frame to stderr, not
+                 counting the time spent waiting for the next frame
+  --export-ppm <DIR>
+                 Also save each frame as an image, DIR/frame_0001.ppm and so on
+  --export-svg <DIR>
+                 Also save the wireframe of each frame as a vector image,
+                 DIR/frame_0001.svg and so on
+  --export-gif <FILE>
+                 Also save the animation as an animated GIF
+  --record <FILE>
+                 Add how the cube is turned and seen in each frame to the end
+                 of FILE, so that the animation can be played back
+  --playback <FILE>
+                 Draw the frames recorded in FILE with --record, exactly as they
+                 were, instead of spinning the cube, and exit after the last one
+  --model <FILE> Spin the model in an OBJ file instead of a built-in shape
+  --fit          Move the middle of the shape to the center of the screen and
+                 scale it to the size of the built-in shapes, for models that
+                 are not centered or are much bigger or smaller
+  --fit-screen   Scale the shape so that it stays on the screen however it
+                 turns
+  --scene <FILE> Set up the shape, camera, light, spin and drawing mode from a
+                 TOML scene file. Options after it override what it sets.
+  --count <N>    Spin N copies of the shape side by side (default 1)
+  --script       Instead of animating, read commands from stdin, one per line,
+                 and write a frame for each render command (see below)
+  -h, --help     Print this help
+
+Keys:
+  Arrow keys     Turn the cube
+  Mouse drag     Turn the cube
+  a              Switch spinning on its own on and off
+  + and -        Zoom in and out by narrowing and widening the field of view
+  f and b        Move the camera forwards to the cube and back, which makes it
+                 look bigger and more in perspective when it is near
+  Space          Pause and resume
+  s              Save the current frame as text in a timestamped file in the
+                 current directory
+  q              Quit
+
+Script commands:
+  yaw <RAD>      Turn the cube around its own y-axis
+  pitch <RAD>    Turn the cube around the x-axis of the screen
+  zoom <X>       Zoom in by a factor, or out if it is less than 1
+  render         Write a frame
+";
+
+/// Settings chosen on the command line
+struct Args {
+    width: usize,
+    height: usize,
+    fixed_size: bool,
+    speed: f32,
+    motion: Motion,
+    rates: [f32; 3],
+    amplitude: f32,
+    frequency: f32,
+    fov: f32,
+    distance: Option<f32>,
+    fps: u32,
+    frames: Option<u64>,
+    glyphs: Glyphs,
+    mode: RenderMode,
+    lights: Vec<Light>,
+    shininess: f32,
+    shape: Shape,
+    model: Option<String>,
+    fit: bool,
+    fit_screen: bool,
+    scene: SceneConfig,
+    count: usize,
+    export_ppm: Option<String>,
+    export_svg: Option<String>,
+    export_gif: Option<String>,
+    record: Option<String>,
+    playback: Option<String>,
+    script: bool,
+    hud: bool,
+    plain: bool,
+    stats: bool,
+    depth_cue: bool,
+    antialias: bool,
+    halo: bool,
+    axes: bool,
+    colors: bool,
+    shadow: bool,
+    views: bool,
+    anaglyph: bool,
+    eye_separation: f32,
+    charset: Charset,
+    background: char,
+    vertices: bool,
+    labels: bool,
+    help: bool,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Args {
+            width: SCREEN_WIDTH,
+            height: SCREEN_HEIGHT,
+            fixed_size: false,
+            speed: 1.0,
+            motion: Motion::Spin,
+            rates: [0.0, ANGULAR_VELOCITY, 0.0],
+            amplitude: 45.0,
+            frequency: 1.0,
+            fov: 90.0,
+            distance: None,
+            fps: 33,
+            frames: None,
+            glyphs: Glyphs::Ascii,
+            mode: RenderMode::Wireframe,
+            lights: Vec::new(),
+            shininess: 16.0,
+            shape: Shape::Cube,
+            model: None,
+            fit: false,
+            fit_screen: false,
+            scene: SceneConfig::default(),
+            count: 1,
+            export_ppm: None,
+            export_svg: None,
+            export_gif: None,
+            record: None,
+            playback: None,
+            script: false,
+            hud: false,
+            plain: false,
+            stats: false,
+            depth_cue: false,
+            antialias: false,
+            halo: false,
+            axes: false,
+            colors: false,
+            shadow: false,
+            views: false,
+            anaglyph: false,
+            eye_separation: 0.2,
+            charset: Charset::default(),
+            background: ' ',
+            vertices: false,
+            labels: false,
+            help: false,
+        }
+    }
+}
+
+/// Parses the command-line arguments, not including the program name
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--width" => (parsed.width, parsed.fixed_size) = (parse_value(&arg, args.next())?, true),
+            "--height" => (parsed.height, parsed.fixed_size) = (parse_value(&arg, args.next())?, true),
+            "--speed" => parsed.speed = parse_value(&arg, args.next())?,
+            "--rx" => parsed.rates[0] = parse_value(&arg, args.next())?,
+            "--ry" => parsed.rates[1] = parse_value(&arg, args.next())?,
+            "--rz" => parsed.rates[2] = parse_value(&arg, args.next())?,
+            "--motion" => parsed.motion = parse_value(&arg, args.next())?,
+            "--amplitude" => parsed.amplitude = parse_value(&arg, args.next())?,
+            "--frequency" => parsed.frequency = parse_value(&arg, args.next())?,
+            "--fov" => parsed.fov = parse_value(&arg, args.next())?,
+            "--distance" => parsed.distance = Some(parse_value(&arg, args.next())?),
+            "--fps" => parsed.fps = parse_value(&arg, args.next())?,
+            "--frames" => parsed.frames = Some(parse_value(&arg, args.next())?),
+            "--glyphs" => parsed.glyphs = parse_value(&arg, args.next())?,
+            "--mode" => parsed.mode = parse_value(&arg, args.next())?,
+            "--light" => parsed.lights.push(parse_value(&arg, args.next())?),
+            "--shininess" => parsed.shininess = parse_value(&arg, args.next())?,
+            "--shape" => parsed.shape = parse_value(&arg, args.next())?,
+            "--model" => parsed.model = Some(parse_value(&arg, args.next())?),
+            "--fit" => parsed.fit = true,
+            "--fit-screen" => parsed.fit_screen = true,
+            #[cfg(feature = "scene")]
+            "--scene" => load_scene(&parse_value::<String>(&arg, args.next())?, &mut parsed)?,
+            #[cfg(not(feature = "scene"))]
+            "--scene" => return Err("--scene cannot be used, since the program was built without the scene feature".to_string()),
+            "--count" => parsed.count = parse_value(&arg, args.next())?,
+            "--export-ppm" => parsed.export_ppm = Some(parse_value(&arg, args.next())?),
+            "--export-svg" => parsed.export_svg = Some(parse_value(&arg, args.next())?),
+            "--export-gif" => parsed.export_gif = Some(parse_value(&arg, args.next())?),
+            "--record" => parsed.record = Some(parse_value(&arg, args.next())?),
+            "--playback" => parsed.playback = Some(parse_value(&arg, args.next())?),
+            "--script" => parsed.script = true,
+            "--hud" => parsed.hud = true,
+            "--plain" => parsed.plain = true,
+            "--stats" => parsed.stats = true,
+            "--depth-cue" => parsed.depth_cue = true,
+            "--antialias" => parsed.antialias = true,
+            "--halo" => parsed.halo = true,
+            "--axes" => parsed.axes = true,
+            "--colors" => parsed.colors = true,
+            "--shadow" => parsed.shadow = true,
+            "--views" => parsed.views = true,
+            "--anaglyph" => parsed.anaglyph = true,
+            "--eye-separation" => parsed.eye_separation = parse_value(&arg, args.next())?,
+            "--charset" => parsed.charset = parse_value(&arg, args.next())?,
+            "--background" => parsed.background = parse_value(&arg, args.next())?,
+            "--vertices" => parsed.vertices = true,
+            "--labels" => parsed.labels = true,
+            "-h" | "--help" => parsed.help = true,
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+    // Nothing is drawn when the help is asked for, so the other settings do not need to make sense.
+    if parsed.help {
+        return Ok(parsed);
+    }
+    if !parsed.fixed_size {
+        [parsed.width, parsed.height] = term::initial_screen(term::terminal_size());
+    }
+    if parsed.width == 0 || parsed.height == 0 {
+        return Err("the screen must be at least 1x1".to_string());
+    }
+    if !(MIN_FOV..=MAX_FOV).contains(&parsed.fov) {
+        return Err(format!("the field of view must be from {} to {} degrees", MIN_FOV, MAX_FOV));
+    }
+    if parsed.distance.is_some_and(|distance| !(MIN_DISTANCE..=MAX_DISTANCE).contains(&distance)) {
+        return Err(format!("the distance must be from {} to {}", MIN_DISTANCE, MAX_DISTANCE));
+    }
+    if parsed.shininess.is_nan() || parsed.shininess < 0.0 {
+        return Err("the shininess must not be negative".to_string());
+    }
+    if parsed.hud && parsed.glyphs != Glyphs::Ascii {
+        return Err("--hud can only be used with ascii glyphs".to_string());
+    }
+    if parsed.background != ' ' && matches!(parsed.glyphs, Glyphs::HalfBlocks | Glyphs::Braille) {
+        return Err("--background can only be used with ascii or box glyphs".to_string());
+    }
+    if parsed.halo && parsed.antialias {
+        return Err("--halo cannot be used with --antialias".to_string());
+    }
+    if parsed.anaglyph && parsed.views {
+        return Err("--anaglyph cannot be used with --views".to_string());
+    }
+    if parsed.script && (parsed.record.is_some() || parsed.playback.is_some()) {
+        return Err("--record and --playback cannot be used with --script".to_string());
+    }
+    if parsed.count == 0 {
+        return Err("there must be at least one copy of the shape".to_string());
+    }
+    Ok(parsed)
+}
+
+/// The built-in shapes that can be spun
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Cube,
+    Tetrahedron,
+    Octahedron,
+    Icosahedron,
+}
+
+impl Shape {
+    fn mesh(self) -> Mesh {
+        match self {
+            Shape::Cube => Mesh::cube(),
+            Shape::Tetrahedron => Mesh::tetrahedron(),
+            Shape::Octahedron => Mesh::octahedron(),
+            Shape::Icosahedron => Mesh::icosahedron(),
         }
-        
-        ///Iterates over the rows of the frame array, which represents the screen, and prints each row to the console as a string. The row variable is created by converting each row of frame to a string using the from_utf8 function from the str module of the std crate. The unwrap function is used to extract the resulting Result value, which represents the success or failure of the conversion. The resulting string is then printed to the console using the println! macro. This has the effect of printing the contents of the frame array to the console, which represents the wireframe representation of the spinning cube.
-        for l in 0..SCREEN_HEIGHT {
-            let row = std::str::from_utf8(&frame[l]).unwrap();
-            println!("{}", row);
+    }
+}
+
+impl FromStr for Shape {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "cube" => Ok(Shape::Cube),
+            "tetrahedron" => Ok(Shape::Tetrahedron),
+            "octahedron" => Ok(Shape::Octahedron),
+            "icosahedron" => Ok(Shape::Icosahedron),
+            _ => Err(()),
         }
+    }
+}
+
+/// Reads a scene file into args. The shape, model, speed and mode it sets replace any given before it.
+#[cfg(feature = "scene")]
+fn load_scene(path: &str, args: &mut Args) -> Result<(), String> {
+    let text = std::fs::read_to_string(path).map_err(|err| format!("cannot read {}: {}", path, err))?;
+    let scene: SceneConfig = text.parse().map_err(|err| format!("invalid scene file {}: {}", path, err))?;
+    if let Some(shape) = &scene.shape {
+        args.shape = shape.parse().map_err(|_| format!("unknown shape '{}' in {}", shape, path))?;
+        args.model = None;
+    }
+    if let Some(model) = &scene.model {
+        args.model = Some(model.clone());
+    }
+    if let Some(speed) = scene.speed {
+        args.speed = speed;
+    }
+    if let Some(mode) = scene.mode {
+        args.mode = mode;
+    }
+    args.scene = scene;
+    Ok(())
+}
 
+/// Parses the value given after a flag
+fn parse_value<T: FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", flag))?;
+    value.parse().map_err(|_| format!("invalid value '{}' for {}", value, flag))
+}
+
+/// Rotation speed of the cube around its y-axis in radians per second when --speed is 1 and --ry is not given
+const ANGULAR_VELOCITY : f32 = 0.33;
+
+/// How the cube moves on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Motion {
+    /// Turning round and round at a steady speed
+    Spin,
+    /// Rocking back and forth, slowing down at each end
+    Oscillate,
+}
 
-        ///Uses the ANSI escape sequence \x1b[{}A to move the cursor up by SCREEN_HEIGHT lines. The print! macro is used to print this escape sequence to the console without a newline character at the end. This has the effect of moving the cursor up by SCREEN_HEIGHT lines, which is useful for creating an animation where the frame is redrawn in the same location on the screen for each iteration of the loop. Without this code, each frame of the animation would be printed on a new line below the previous frame, causing the animation to scroll down the screen.
-        print!("\x1b[{}A;", SCREEN_HEIGHT);
+impl FromStr for Motion {
+    type Err = ();
 
-        std::thread::sleep(std::time::Duration::from_millis(30));
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "spin" => Ok(Motion::Spin),
+            "oscillate" => Ok(Motion::Oscillate),
+            _ => Err(()),
+        }
     }
 }
 
+/// Time between frames if every frame takes exactly as long as it is meant to at the given frame rate. Unthrottled animations are timed as if they ran at the default frame rate.
+fn nominal_interval(fps: u32) -> Duration {
+    let fps = if fps > 0 { fps } else { Args::default().fps };
+    Duration::from_secs_f64(1.0 / fps as f64)
+}
+
+/// The controls as they are at the start, with the field of view and the distance of the camera from the command line or the scene file
+fn initial_controls(args: &Args) -> Controls {
+    Controls::new(args.fov, args.distance.unwrap_or_else(|| args.scene.camera.distance()))
+}
+
+/// Width of the row the copies of the model are lined up in, in world units
+const ROW_WIDTH : f32 = 5.0;
+
+/// Transformation matrix that scales down and places copy i of count copies of the model in a row across the view
+fn copy_placement(i: usize, count: usize) -> Matrix {
+    let size = 1.0 / count as f32;
+    let x = (i as f32 + 0.5 - count as f32 / 2.0) * ROW_WIDTH * size;
+    translation(x, 0.0, 0.0) * scale(size, size, size)
+}
+
+/// Size in pixels of each character of the screen in exported images. Characters are about twice as tall as they are wide.
+const CHAR_PIXELS : [usize; 2] = [8, 16];
 
-///Determines whether a triangle formed by three 2D coordinates should be drawn by calculating the cross product of the edges of the triangle. If the cross product is positive, the triangle is culled (not drawn). If the cross product is negative, the triangle is not culled (drawn).
-fn cull(p0: [f32; 2], p1: [f32; 2], p2: [f32; 2]) -> bool {
-    let dx = [p1[0] - p0[0], p2[0] - p1[0]];
-    let dy = [p1[1] - p0[1], p2[1] - p1[1]];
-    dx[0] * dy[1] > dx[1] * dy[0]
+/// Reads a mesh from an OBJ file
+fn load_model(path: &str) -> Result<Mesh, String> {
+    let text = std::fs::read_to_string(path).map_err(|err| format!("cannot read {}: {}", path, err))?;
+    parse_obj(&text).map_err(|err| format!("{}: {}", path, err))
 }
 
-///The draw_line function draws a line between two 2D coordinates in a 2D array of characters representing the screen. It does this by iterating over either the x or y coordinates of the line, calculating the corresponding x or y coordinates, and drawing horizontal or vertical lines in the array at these coordinates.
-fn draw_line(frame: &mut [[u8; SCREEN_WIDTH]; SCREEN_HEIGHT], start: [f32; 2], end: [f32; 2]) {
-    let [x0, y0] = start;
-    let [x1, y1] = end;
-    let [dx, dy] = [x1 - x0, y1 - y0];
-    if dy.abs() > dx.abs() {
-        let ymin = y0.min(y1);
-        let ymax = y0.max(y1);
-        let iymin = ymin.ceil() as usize;
-        let iymax = ymax.ceil() as usize;
-        let dxdy = dx / dy;
-        for iy in iymin..iymax {
-            let ix = ((iy as f32 - y0) * dxdy + x0) as usize;
-            frame[iy][ix] = b'|';
+/// Reads the frames recorded in a file with --record
+fn load_recording(path: &str) -> Result<Vec<FrameState>, String> {
+    let text = std::fs::read_to_string(path).map_err(|err| format!("cannot read {}: {}", path, err))?;
+    parse_recording(&text).map_err(|err| format!("{}: {}", path, err))
+}
+
+fn main() {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if args.help {
+        print!("{}", USAGE);
+        return;
+    }
+
+    let mut mesh = match &args.model {
+        Some(path) => match load_model(path) {
+            Ok(mesh) => mesh,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+        },
+        None => args.shape.mesh(),
+    };
+    if args.fit {
+        let fit = mesh.fit_transform();
+        for vertex in &mut mesh.vertices {
+            *vertex = fit * *vertex;
         }
+    }
+    let playback = match &args.playback {
+        Some(path) => match load_recording(path) {
+            Ok(states) => Some(states),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // Errors are only reported once animate has restored the terminal, so that they are not lost with the alternate screen.
+    let mut stats = RenderStats::new();
+    let result = if args.script { run_script(&args, &mesh, &mut stats) } else { animate(&args, &mesh, playback.as_deref(), &mut stats) };
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+    if let Some(summary) = stats.summary().filter(|_| args.stats) {
+        eprint!("{}", summary);
+    }
+    // Ctrl-C only ends the animation, so that the terminal is restored as usual, but the program still exits as if it had been interrupted.
+    if term::interrupted() {
+        std::process::exit(term::INTERRUPTED_STATUS);
+    }
+}
+
+/// Transformation matrices that turn each copy of the model as steered around its own center and shrink the copies so that they fit side by side where one would be on its own, around the origin. With --fit-screen, the whole row is scaled to stay on the screen as seen with the options, in a frame of width by height cells.
+fn place_models<'a>(args: &Args, mesh: &'a Mesh, controls: &Controls, options: &RenderOptions, [width, height]: [usize; 2]) -> Vec<Model<'a>> {
+    let fit = if args.fit_screen {
+        let origin = Vector([0.0, 0.0, 0.0, 1.0]);
+        // Every copy turns inside a sphere around its own center, so the row turns inside a sphere around the origin that reaches out to the farthest of those.
+        let radius = (0..args.count).map(|i| {
+            length(&(copy_placement(i, args.count) * origin)) + mesh.radius() / args.count as f32
+        }).fold(0.0, f32::max);
+        let size = screen_fit_scale(radius, length(&(options.view * origin)), options, width, height);
+        scale(size, size, size)
     } else {
-        let xmin = x0.min(x1);
-        let xmax = x0.max(x1);
-        let ixmin = xmin.ceil() as usize;
-        let ixmax = xmax.ceil() as usize;
-        let dydx = dy / dx;
-        for ix in ixmin..ixmax {
-            let iy = ((ix as f32 - x0) * dydx + y0) as usize;
-            frame[iy][ix] = b'-';
+        IDENTITY
+    };
+    (0..args.count).map(|i| Model {
+        mesh,
+        model_to_world: fit * copy_placement(i, args.count) * controls.orientation.to_matrix(),
+    }).collect()
+}
+
+/// Render options for the settings on the command line and the current zoom and distance
+fn render_options(args: &Args, controls: &Controls) -> RenderOptions {
+    // The frame has a cell for every part of a character the glyphs can show, so with half blocks it has twice as many rows and each of its cells is half as tall as a character, and with Braille it has twice as many columns and four times as many rows.
+    let (across, down) = args.glyphs.cell_size();
+    let defaults = args.scene.render_options();
+    RenderOptions {
+        view: args.scene.camera.dolly(controls.distance).view(),
+        fov_y: controls.fov.to_radians(),
+        cell_aspect: defaults.cell_aspect * down as f32 / across as f32,
+        mode: args.mode,
+        charset: args.charset,
+        background: args.background,
+        depth_cue: args.depth_cue,
+        antialias: args.antialias,
+        halo: args.halo,
+        // The edges at the back are what the halos are for, so they are drawn too.
+        cull_mode: if args.halo { CullMode::None } else { defaults.cull_mode },
+        axes: args.axes,
+        colors: if args.colors { Some(FACE_COLORS) } else { defaults.colors },
+        shadow: args.shadow,
+        vertex_markers: args.vertices,
+        vertex_labels: args.labels,
+        lights: if args.lights.is_empty() { defaults.lights } else { args.lights.clone() },
+        shininess: args.shininess,
+        ..defaults
+    }
+}
+
+/// Draws the models into the frame, split into the views of the blueprint with --views or for 3D glasses with --anaglyph
+fn render(args: &Args, frame: &mut Frame, models: &[Model], options: &RenderOptions) {
+    if args.views {
+        render_views_into(frame, models, options);
+    } else if args.anaglyph {
+        render_anaglyph_into(frame, models, options, args.eye_separation);
+    } else {
+        render_scene_into(frame, models, options);
+    }
+}
+
+/// A line of a script read by --script
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    /// Turn the cube by this many radians like rotation_y around its own y-axis
+    Yaw(f32),
+    /// Turn the cube by this many radians like rotation_x around the x-axis of the screen
+    Pitch(f32),
+    /// Zoom in by this factor, or out if it is less than 1
+    Zoom(f32),
+    /// Draw a frame
+    Render,
+}
+
+/// Parses a line of a script, which is None if it is blank or a comment starting with '#'
+fn parse_command(line: &str) -> Result<Option<Command>, String> {
+    let mut words = line.split_whitespace();
+    let Some(name) = words.next().filter(|name| !name.starts_with('#')) else {
+        return Ok(None);
+    };
+    let mut value = || parse_value::<f32>(name, words.next().map(str::to_string));
+    let command = match name {
+        "yaw" => Command::Yaw(value()?),
+        "pitch" => Command::Pitch(value()?),
+        "zoom" => Command::Zoom(value()?),
+        "render" => Command::Render,
+        _ => return Err(format!("unknown command '{}'", name)),
+    };
+    if words.next().is_some() {
+        return Err(format!("too many values for {}", name));
+    }
+    Ok(Some(command))
+}
+
+/// Runs the commands of a script read from stdin, one per line, writing a frame to stdout for every render command. Nothing else is written, and the frames are simply written one after another, so that the output of a script is always the same.
+fn run_script(args: &Args, mesh: &Mesh, stats: &mut RenderStats) -> Result<(), String> {
+    let (across, down) = args.glyphs.cell_size();
+    let mut controls = initial_controls(args);
+    let mut frame = Frame::new(args.width * across, args.height * down);
+    let mut frame_number = 0;
+    let mut stdout = std::io::stdout().lock();
+    let mut output = Vec::new();
+    for (line_number, line) in std::io::stdin().lock().lines().enumerate() {
+        let line = line.map_err(|err| format!("cannot read the script: {}", err))?;
+        let command = parse_command(&line).map_err(|err| format!("line {} of the script: {}", line_number + 1, err))?;
+        match command {
+            Some(Command::Yaw(angle)) => controls.turn(angle, 0.0),
+            Some(Command::Pitch(angle)) => controls.turn(0.0, angle),
+            Some(Command::Zoom(factor)) => controls.zoom(factor),
+            Some(Command::Render) => {
+                let options = render_options(args, &controls);
+                let models = place_models(args, mesh, &controls, &options, [frame.width(), frame.height()]);
+                let started = Instant::now();
+                render(args, &mut frame, &models, &options);
+                stats.add(started.elapsed());
+                if args.hud {
+                    frame.write_text(0, 0, &hud_text(None, frame_number));
+                }
+                output.clear();
+                // The frames of a script are never drawn over one another, so each is written as though it were the first, apart from the blank line between plain frames.
+                match args.plain {
+                    true => Output::Plain.compose_frame(&frame, args.glyphs, frame_number == 0, &mut output),
+                    false => Output::Terminal.compose_frame(&frame, args.glyphs, true, &mut output),
+                }
+                frame_number += 1;
+                if stdout.write_all(&output).and_then(|()| stdout.flush()).is_err() {
+                    break;
+                }
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Runs the animation until it is done or the user quits, or plays back the recorded frames if there are any
+fn animate(args: &Args, mesh: &Mesh, playback: Option<&[FrameState]>, stats: &mut RenderStats) -> Result<(), String> {
+    let (across, down) = args.glyphs.cell_size();
+    let cell_pixels = [CHAR_PIXELS[0] / across, CHAR_PIXELS[1] / down];
+    let mut gif = match &args.export_gif {
+        Some(path) => {
+            let file = File::create(path).map_err(|err| format!("cannot create {}: {}", path, err))?;
+            let gif = GifExport::new(BufWriter::new(file), [args.width * across, args.height * down], cell_pixels, nominal_interval(args.fps));
+            Some((gif.map_err(|err| format!("cannot write {}: {}", path, err))?, path))
+        }
+        None => None,
+    };
+    let mut record = match &args.record {
+        Some(path) => {
+            let file = File::options().create(true).append(true).open(path).map_err(|err| format!("cannot open {}: {}", path, err))?;
+            Some((BufWriter::new(file), path))
         }
+        None => None,
+    };
+
+    // The terminal is restored when these guards go out of scope at the end of animate, in the reverse order: the cursor is shown again and then the main screen comes back. Plain output leaves the terminal as it is, and is also what goes into a pipe or a file, where escape sequences would only be garbage.
+    let style = Output::choose(args.plain, term::stdout_is_terminal);
+    let plain = style == Output::Plain;
+    let _screen = (!plain).then(AlternateScreen::new);
+    let _cursor = (!plain).then(HiddenCursor::new);
+    term::catch_interrupt();
+    term::catch_resize();
+
+    // Keyboard and mouse control only work if stdin is a terminal; otherwise the cube just spins. Turning on mouse reporting takes an escape sequence, so plain output only has the keyboard.
+    let raw_mode = RawMode::new().ok();
+    let _mouse = raw_mode.as_ref().filter(|_| !plain).map(|_| MouseReporting::new());
+    let mut controls = initial_controls(args);
+
+    // The animation follows the wall clock, so the cube turns at the same speed however long each frame takes to draw. When a fixed number of frames is drawn, they are timed by the frame rate instead, so the same frames are drawn every time.
+    let mut last_frame = Instant::now();
+    // How far the cube has got through its motion, in seconds at normal speed, which stands still while it is not spinning
+    let mut motion_time = 0.0;
+    let mut stdout = std::io::stdout().lock();
+    let mut output = Vec::new();
+    // Every frame is drawn into the same frame buffer, which only has to be allocated once.
+    let mut frame = Frame::new(args.width * across, args.height * down);
+    // Size of the screen in characters, which changes with the terminal unless it was chosen on the command line. The frames of a GIF must all be the same size, so it does not change while one is being saved either, and nor does it with plain output, which would need an escape sequence to clear the screen.
+    let mut screen = [args.width, args.height];
+    let follow_terminal = !args.fixed_size && gif.is_none() && !plain;
+    // Whether the next frame is drawn from the top of the terminal rather than over the previous one
+    let mut redraw = true;
+    for frame_number in 0..args.frames.unwrap_or(u64::MAX) {
+        if term::interrupted() {
+            break;
+        }
+
+        // After a resize, the frame is reallocated for the new size and the screen is cleared, since the terminal may have moved or cut off what was drawn before. The projection follows the size of the frame, so the cube stays centered.
+        if term::take_resized() && follow_terminal {
+            if let Ok(size) = term::terminal_size() {
+                screen = term::screen_for_terminal(size);
+                frame.resize(screen[0] * across, screen[1] * down);
+                output.extend_from_slice(term::CLEAR_SCREEN.as_bytes());
+                redraw = true;
+            }
+        }
+
+        if let Some(raw_mode) = &raw_mode {
+            for event in parse_input(&raw_mode.read_input()) {
+                controls.handle_event(event);
+            }
+        }
+        if controls.quit {
+            break;
+        }
+
+        // Time passed since the previous frame, and the time the animation moves on by
+        let now = Instant::now();
+        let frame_time = (frame_number > 0).then(|| now - last_frame);
+        let elapsed = if frame_number == 0 {
+            Duration::ZERO
+        } else if args.frames.is_some() {
+            nominal_interval(args.fps)
+        } else {
+            now - last_frame
+        };
+        last_frame = now;
+        if let Some(playback) = playback {
+            // A recording replaces the motion and the controls, apart from quitting, and ends the animation when it runs out.
+            let Some(state) = playback.get(frame_number as usize) else {
+                break;
+            };
+            controls.orientation = state.orientation;
+            controls.fov = state.fov;
+            controls.distance = state.distance;
+        } else if controls.spinning() {
+            // The cube is turned on by the difference between where it should be in its motion now and where it was, which leaves any turns made with the keys or mouse in place.
+            let time = motion_time + elapsed.as_secs_f32() * args.speed;
+            let [rx, ry, rz] = args.rates;
+            match args.motion {
+                Motion::Spin if rx == 0.0 && rz == 0.0 => controls.spin(&args.scene.spin_axis(), angle_at(elapsed, ry * args.speed)),
+                Motion::Spin => controls.turn_by(tumble(motion_time, args.rates).conjugate() * tumble(time, args.rates)),
+                Motion::Oscillate => {
+                    let swing = |t| oscillation(t, args.amplitude.to_radians(), args.frequency);
+                    controls.spin(&args.scene.spin_axis(), swing(time) - swing(motion_time));
+                }
+            }
+            motion_time = time;
+        }
+
+        if let Some((record, path)) = &mut record {
+            let state = FrameState { orientation: controls.orientation, fov: controls.fov, distance: controls.distance };
+            writeln!(record, "{}", state).map_err(|err| format!("cannot write {}: {}", path, err))?;
+        }
+
+        let options = render_options(args, &controls);
+        let models = place_models(args, mesh, &controls, &options, [frame.width(), frame.height()]);
+        let started = Instant::now();
+        render(args, &mut frame, &models, &options);
+        stats.add(started.elapsed());
+        if args.hud {
+            frame.write_text(0, 0, &hud_text(frame_time, frame_number));
+        }
+        if std::mem::take(&mut controls.screenshot) {
+            save_screenshot(std::path::Path::new("."), &frame, SystemTime::now()).map_err(|err| format!("cannot save a screenshot: {}", err))?;
+        }
+        if let Some(dir) = &args.export_ppm {
+            let path = std::path::Path::new(dir).join(format!("frame_{:04}.ppm", frame_number + 1));
+            std::fs::write(&path, frame_to_ppm(&frame, cell_pixels)).map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+        }
+
+        if let Some(dir) = &args.export_svg {
+            let path = std::path::Path::new(dir).join(format!("frame_{:04}.svg", frame_number + 1));
+            let svg = frame_to_svg(&models, &options, screen[0] * across, screen[1] * down, cell_pixels);
+            std::fs::write(&path, svg).map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+        }
+        if let Some((gif, path)) = &mut gif {
+            gif.add_frame(&frame).map_err(|err| format!("cannot write {}: {}", path, err))?;
+        }
+
+        // Output errors, such as the other end of a pipe closing, are not worth a panic, so they just end the animation.
+        style.compose_frame(&frame, args.glyphs, redraw, &mut output);
+        redraw = false;
+        if stdout.write_all(&output).and_then(|()| stdout.flush()).is_err() {
+            break;
+        }
+        output.clear();
+
+        if let Some(delay) = frame_delay(args.fps) {
+            std::thread::sleep(delay);
+        }
+    }
+    if let Some((record, path)) = &mut record {
+        record.flush().map_err(|err| format!("cannot write {}: {}", path, err))?;
+    }
+    if let Some((gif, path)) = gif {
+        gif.finish().map_err(|err| format!("cannot write {}: {}", path, err))?;
     }
+    Ok(())
 }
@@ -0,0 +1,63 @@
+//! Loading of meshes from Wavefront OBJ files
+
+use std::fmt;
+
+use crate::{Mesh, Vector};
+
+/// Why an OBJ file could not be loaded, and on which line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjError {
+    /// Number of the line with the problem, counting from 1
+    pub line: usize,
+    /// What is wrong with the line
+    pub message: String,
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ObjError {}
+
+/// Parses the text of an OBJ file into a mesh. Only the vertex positions on `v` lines and the faces on `f` lines are read, and everything else is ignored. Faces may have any number of corners from three up, and the texture and normal indices after a slash in each corner are skipped. Indices counting back from the end of the vertex list, which are negative, are not supported. Faces are turned to be wound clockwise like the faces of Mesh.
+pub fn parse_obj(text: &str) -> Result<Mesh, ObjError> {
+    let mut mesh = Mesh { vertices: Vec::new(), faces: Vec::new() };
+    for (line_index, line) in text.lines().enumerate() {
+        let error = |message: String| ObjError { line: line_index + 1, message };
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("v") => {
+                let mut position = [0.0, 0.0, 0.0, 1.0];
+                for coordinate in position.iter_mut().take(3) {
+                    let field = fields.next().ok_or_else(|| error("a vertex needs x, y and z coordinates".to_string()))?;
+                    *coordinate = field.parse().map_err(|_| error(format!("invalid coordinate '{}'", field)))?;
+                }
+                mesh.vertices.push(Vector(position));
+            }
+            Some("f") => {
+                let mut face = Vec::new();
+                for field in fields {
+                    let index = field.split('/').next().unwrap_or(field);
+                    let index: i64 = index.parse().map_err(|_| error(format!("invalid vertex index '{}'", field)))?;
+                    if index < 0 {
+                        return Err(error(format!("negative vertex index {} is not supported", index)));
+                    }
+                    if index == 0 || index as usize > mesh.vertices.len() {
+                        return Err(error(format!("vertex index {} is out of range", index)));
+                    }
+                    face.push(index as usize - 1);
+                }
+                if face.len() < 3 {
+                    return Err(error("a face needs at least three vertices".to_string()));
+                }
+                // OBJ files wind faces counterclockwise when seen from outside, the opposite way to Mesh.
+                face.reverse();
+                mesh.faces.push(face);
+            }
+            _ => {}
+        }
+    }
+    Ok(mesh)
+}
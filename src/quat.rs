@@ -0,0 +1,103 @@
+//! Quaternions for turning the cube without gimbal lock
+
+use crate::{normalize, Matrix, Vector, EPSILON};
+
+/// A quaternion (x, y, z, w), with the vector part first like the components of a Vector. Unit quaternions stand for rotations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat(pub [f32; 4]);
+
+impl Quat {
+    /// The quaternion that does not rotate at all
+    pub const IDENTITY : Quat = Quat([0.0, 0.0, 0.0, 1.0]);
+
+    /// Builds the unit quaternion that rotates by theta radians around an axis, the same way as rotation_axis. The axis does not need to be of unit length.
+    pub fn from_axis_angle(axis: &Vector, theta: f32) -> Self {
+        let [x, y, z, _] = normalize(axis).0;
+        // rotation_axis turns clockwise, the opposite way to the usual quaternion for an axis and angle, so the angle is negated.
+        let (s, c) = (-theta / 2.0).sin_cos();
+        Quat([x * s, y * s, z * s, c])
+    }
+
+    /// Scales the quaternion to unit length, so that it is a rotation again after rounding errors have built up. A quaternion too short to have a direction becomes the identity.
+    pub fn normalize(&self) -> Self {
+        let [x, y, z, w] = self.0;
+        let len = (x * x + y * y + z * z + w * w).sqrt();
+        if len < EPSILON {
+            return Quat::IDENTITY;
+        }
+        Quat([x / len, y / len, z / len, w / len])
+    }
+
+    /// Returns the conjugate of the quaternion, which for a unit quaternion is the rotation that undoes it
+    pub fn conjugate(&self) -> Self {
+        let [x, y, z, w] = self.0;
+        Quat([-x, -y, -z, w])
+    }
+
+    /// Builds the rotation matrix of a unit quaternion
+    pub fn to_matrix(&self) -> Matrix {
+        let [x, y, z, w] = self.0;
+        Matrix([
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y + z * w), 2.0 * (x * z - y * w), 0.0],
+            [2.0 * (x * y - z * w), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z + x * w), 0.0],
+            [2.0 * (x * z + y * w), 2.0 * (y * z - x * w), 1.0 - 2.0 * (x * x + y * y), 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+/// Orientation after tumbling for time t at rates of rx, ry and rz radians per unit of time around the x-, y- and z-axes. It is the same rotation as rotation_y(t * ry) * rotation_x(t * rx) * rotation_z(t * rz).
+pub fn tumble(t: f32, [rx, ry, rz]: [f32; 3]) -> Quat {
+    // from_axis_angle turns the same way as rotation_y but the opposite way to rotation_x and rotation_z.
+    let x = Quat::from_axis_angle(&Vector([1.0, 0.0, 0.0, 0.0]), -t * rx);
+    let y = Quat::from_axis_angle(&Vector([0.0, 1.0, 0.0, 0.0]), t * ry);
+    let z = Quat::from_axis_angle(&Vector([0.0, 0.0, 1.0, 0.0]), -t * rz);
+    y * x * z
+}
+
+/// Computes the dot product of two quaternions, which for unit quaternions is the cosine of half the angle between the rotations
+pub fn quat_dot(a: &Quat, b: &Quat) -> f32 {
+    a.0.iter().zip(b.0).map(|(a, b)| a * b).sum()
+}
+
+/// Interpolates between the rotations a and b along the shortest arc between them, at a constant speed as t goes from 0 at a to 1 at b. Rotations that are nearly the same are interpolated linearly instead, since the arc between them is too short to divide by.
+pub fn slerp(a: &Quat, b: &Quat, t: f32) -> Quat {
+    // q and -q are the same rotation, so b is flipped if that takes the shorter way round.
+    let mut cos_angle = quat_dot(a, b);
+    let mut b = b.0;
+    if cos_angle < 0.0 {
+        cos_angle = -cos_angle;
+        b = b.map(|c| -c);
+    }
+    let (wa, wb) = if cos_angle > 1.0 - SLERP_EPSILON {
+        (1.0 - t, t)
+    } else {
+        let angle = cos_angle.acos();
+        let sin_angle = angle.sin();
+        (((1.0 - t) * angle).sin() / sin_angle, (t * angle).sin() / sin_angle)
+    };
+    let mut q = [0.0; 4];
+    for ((q, a), b) in q.iter_mut().zip(a.0).zip(b) {
+        *q = wa * a + wb * b;
+    }
+    Quat(q).normalize()
+}
+
+/// How close the cosine of the angle between two rotations must be to 1 for slerp to interpolate them linearly
+const SLERP_EPSILON : f32 = 1e-4;
+
+/// Multiplies two quaternions. Like the product of two matrices, the product rotates by b first and then by a.
+impl std::ops::Mul<Quat> for Quat {
+    type Output = Quat;
+
+    fn mul(self, b: Quat) -> Quat {
+        let [ax, ay, az, aw] = self.0;
+        let [bx, by, bz, bw] = b.0;
+        Quat([
+            aw * bx + ax * bw + ay * bz - az * by,
+            aw * by - ax * bz + ay * bw + az * bx,
+            aw * bz + ax * by - ay * bx + az * bw,
+            aw * bw - ax * bx - ay * by - az * bz,
+        ])
+    }
+}
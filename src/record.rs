@@ -0,0 +1,61 @@
+//! Recordings of how the cube was turned and seen in each frame of an animation, so that it can be played back exactly
+
+use std::fmt;
+
+use crate::quat::Quat;
+
+/// How the cube was turned and seen in one frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameState {
+    /// How the cube was turned
+    pub orientation: Quat,
+    /// Vertical field of view of the camera in degrees
+    pub fov: f32,
+    /// How far the camera is from the cube
+    pub distance: f32,
+}
+
+/// Writes the state as a line of a recording: the x, y, z and w of the orientation then the field of view and then the distance of the camera, separated by spaces. The numbers are written with as many digits as it takes to read them back exactly.
+impl fmt::Display for FrameState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [x, y, z, w] = self.orientation.0;
+        write!(f, "{} {} {} {} {} {}", x, y, z, w, self.fov, self.distance)
+    }
+}
+
+impl std::str::FromStr for FrameState {
+    type Err = String;
+
+    /// Parses a line of a recording as written by Display
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let numbers = line.split_whitespace().map(|field| field.parse().map_err(|_| format!("invalid number '{}'", field))).collect::<Result<Vec<f32>, _>>()?;
+        match *numbers {
+            [x, y, z, w, fov, distance] => Ok(FrameState { orientation: Quat([x, y, z, w]), fov, distance }),
+            _ => Err(format!("a frame needs an orientation x y z w, a field of view and a distance, not {} numbers", numbers.len())),
+        }
+    }
+}
+
+/// Why a recording could not be read, and on which line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingError {
+    /// Number of the line with the problem, counting from 1
+    pub line: usize,
+    /// What is wrong with the line
+    pub message: String,
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+/// Parses the text of a recording, which has the state of one frame on each line in the order they were drawn. Blank lines are skipped.
+pub fn parse_recording(text: &str) -> Result<Vec<FrameState>, RecordingError> {
+    text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).map(|(line_index, line)| {
+        line.parse().map_err(|message| RecordingError { line: line_index + 1, message })
+    }).collect()
+}
@@ -0,0 +1,123 @@
+//! Scene files, which set up what is spun and how it is seen in TOML. The settings are always available, with the defaults used when no scene file is given, but reading them from a file needs the scene feature.
+
+#[cfg(feature = "scene")]
+use std::str::FromStr;
+
+use crate::{add, length, look_at, scale_vec, sub, Light, Matrix, RenderMode, RenderOptions, Vector};
+
+/// Everything a scene file can set. Any field left out of the file keeps the value it has by default, which is the same as when no scene file is used.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "scene", derive(serde::Deserialize), serde(default, deny_unknown_fields))]
+pub struct SceneConfig {
+    /// Name of a built-in shape to spin, such as "cube"
+    pub shape: Option<String>,
+    /// Path of an OBJ file to spin instead of a built-in shape
+    pub model: Option<String>,
+    /// Where the camera is and what it looks at
+    pub camera: CameraConfig,
+    /// Direction the light comes from in world coordinates, or None for no lighting
+    pub light: Option<[f32; 3]>,
+    /// Axis of the shape that it spins around
+    pub axis: [f32; 3],
+    /// Multiplier for the rotation speed, like --speed
+    pub speed: Option<f32>,
+    /// How the shape is drawn, like --mode
+    pub mode: Option<RenderMode>,
+}
+
+/// Placement of the camera in a scene
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "scene", derive(serde::Deserialize), serde(default, deny_unknown_fields))]
+pub struct CameraConfig {
+    /// Position of the camera in world coordinates
+    pub position: [f32; 3],
+    /// Point the camera looks at
+    pub target: [f32; 3],
+    /// Direction that is up on the screen
+    pub up: [f32; 3],
+}
+
+impl Default for SceneConfig {
+    fn default() -> Self {
+        SceneConfig {
+            shape: None,
+            model: None,
+            camera: CameraConfig::default(),
+            light: None,
+            axis: [0.0, 1.0, 0.0],
+            speed: None,
+            mode: None,
+        }
+    }
+}
+
+/// By default the camera looks at the origin from a little way along the z-axis, so that the shape fills most of the screen.
+impl Default for CameraConfig {
+    fn default() -> Self {
+        CameraConfig {
+            position: [0.0, 0.0, 2.5],
+            target: [0.0, 0.0, 0.0],
+            up: [0.0, 1.0, 0.0],
+        }
+    }
+}
+
+impl CameraConfig {
+    /// View matrix for looking from the position of the camera towards its target
+    pub fn view(&self) -> Matrix {
+        look_at(&point(self.position), &point(self.target), &direction(self.up))
+    }
+
+    /// How far the camera is from its target
+    pub fn distance(&self) -> f32 {
+        length(&sub(&point(self.position), &point(self.target)))
+    }
+
+    /// The same camera moved along the line to its target, nearer to or farther from it, so that it is distance away. A camera on its target stays where it is, since it has no line to move along.
+    pub fn dolly(&self, distance: f32) -> CameraConfig {
+        let (position, target) = (point(self.position), point(self.target));
+        let current = self.distance();
+        if current == 0.0 {
+            return *self;
+        }
+        let Vector([x, y, z, _]) = add(&target, &scale_vec(&sub(&position, &target), distance / current));
+        CameraConfig { position: [x, y, z], ..*self }
+    }
+}
+
+/// A point in world coordinates
+fn point([x, y, z]: [f32; 3]) -> Vector {
+    Vector([x, y, z, 1.0])
+}
+
+/// A direction in world coordinates
+fn direction([x, y, z]: [f32; 3]) -> Vector {
+    Vector([x, y, z, 0.0])
+}
+
+impl SceneConfig {
+    /// Render options that show the scene: the view from its camera, its light and its mode, with everything else as by default
+    pub fn render_options(&self) -> RenderOptions {
+        RenderOptions {
+            view: self.camera.view(),
+            mode: self.mode.unwrap_or(RenderMode::Wireframe),
+            lights: self.light.map(|light| Light::new(direction(light))).into_iter().collect(),
+            ..RenderOptions::default()
+        }
+    }
+
+    /// Axis of the shape that it spins around, as a direction
+    pub fn spin_axis(&self) -> Vector {
+        direction(self.axis)
+    }
+}
+
+/// Parses a scene file, giving the reason it is not valid if it is not
+#[cfg(feature = "scene")]
+impl FromStr for SceneConfig {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        toml::from_str(text).map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,72 @@
+//! Statistics of how long frames take to render, for --stats
+
+use std::fmt;
+use std::time::Duration;
+
+/// The render times of the frames drawn so far
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderStats {
+    samples: Vec<Duration>,
+}
+
+impl RenderStats {
+    /// Starts with no frames
+    pub fn new() -> Self {
+        RenderStats::default()
+    }
+
+    /// Adds the time one frame took to render
+    pub fn add(&mut self, sample: Duration) {
+        self.samples.push(sample);
+    }
+
+    /// Sums up the render times, or None if no frames were rendered
+    pub fn summary(&self) -> Option<StatsSummary> {
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        let (&min, &max) = (sorted.first()?, sorted.last()?);
+        let total: Duration = sorted.iter().sum();
+        // The nearest-rank percentile is the smallest sample that at least that percentage of the samples are no bigger than.
+        let percentile = |p: usize| sorted[(p * sorted.len()).div_ceil(100).max(1) - 1];
+        Some(StatsSummary {
+            frames: sorted.len(),
+            min,
+            max,
+            mean: total / sorted.len() as u32,
+            median: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+        })
+    }
+}
+
+/// The minimum, maximum, mean and percentiles of the render times of a number of frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatsSummary {
+    /// How many frames were rendered
+    pub frames: usize,
+    /// The shortest render time
+    pub min: Duration,
+    /// The longest render time
+    pub max: Duration,
+    /// The mean render time
+    pub mean: Duration,
+    /// The render time half of the frames took no longer than
+    pub median: Duration,
+    /// The render time 90% of the frames took no longer than
+    pub p90: Duration,
+    /// The render time 99% of the frames took no longer than
+    pub p99: Duration,
+}
+
+/// Writes the summary as a heading and then a line for each statistic in milliseconds, each line ending in a newline
+impl fmt::Display for StatsSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "render time of {} frames:", self.frames)?;
+        let rows = [("min", self.min), ("mean", self.mean), ("median", self.median), ("p90", self.p90), ("p99", self.p99), ("max", self.max)];
+        for (name, time) in rows {
+            writeln!(f, "  {:<7}{:.3} ms", name, time.as_secs_f64() * 1000.0)?;
+        }
+        Ok(())
+    }
+}
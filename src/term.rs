@@ -0,0 +1,304 @@
+//! Control of the terminal the cube is drawn in
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{Frame, Glyphs};
+
+/// Set by the SIGINT handler when Ctrl-C is pressed
+static INTERRUPTED : AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_interrupt(_signal: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+/// Installs a handler for Ctrl-C, so that instead of killing the program it only sets a flag that can be checked with interrupted. This lets the program leave its main loop normally and restore the terminal.
+pub fn catch_interrupt() {
+    let handler: extern "C" fn(libc::c_int) = on_interrupt;
+    // SAFETY: the handler only stores to an atomic, which is async-signal-safe.
+    unsafe {
+        libc::signal(libc::SIGINT, handler as libc::sighandler_t);
+    }
+}
+
+/// Exit status of the program after Ctrl-C, the same as a shell gives a program killed by SIGINT
+pub const INTERRUPTED_STATUS : i32 = 128 + libc::SIGINT;
+
+/// Returns true once Ctrl-C has been pressed after catch_interrupt was called
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Set by the SIGWINCH handler when the terminal is resized
+static RESIZED : AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_resize(_signal: libc::c_int) {
+    RESIZED.store(true, Ordering::SeqCst);
+}
+
+/// Installs a handler for the signal sent when the terminal is resized, which sets a flag that can be checked with take_resized
+pub fn catch_resize() {
+    let handler: extern "C" fn(libc::c_int) = on_resize;
+    // SAFETY: the handler only stores to an atomic, which is async-signal-safe.
+    unsafe {
+        libc::signal(libc::SIGWINCH, handler as libc::sighandler_t);
+    }
+}
+
+/// Returns true if the terminal has been resized since the last call, after catch_resize was called
+pub fn take_resized() -> bool {
+    RESIZED.swap(false, Ordering::SeqCst)
+}
+
+/// Returns the size of the terminal on stdout as its number of columns and rows. Fails if stdout is not a terminal or the terminal does not know its size.
+pub fn terminal_size() -> io::Result<[usize; 2]> {
+    let mut size = std::mem::MaybeUninit::<libc::winsize>::uninit();
+    // SAFETY: TIOCGWINSZ fills in the winsize struct when it succeeds.
+    let size = unsafe {
+        if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, size.as_mut_ptr()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        size.assume_init()
+    };
+    if size.ws_col == 0 || size.ws_row == 0 {
+        return Err(io::Error::other("the terminal does not know its size"));
+    }
+    Ok([size.ws_col as usize, size.ws_row as usize])
+}
+
+/// Returns true if stdout is a terminal, and false if it is piped into another program or redirected into a file
+pub fn stdout_is_terminal() -> bool {
+    // SAFETY: isatty only looks up the file descriptor.
+    unsafe { libc::isatty(libc::STDOUT_FILENO) == 1 }
+}
+
+/// How the frames of the animation are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    /// Each frame is drawn over the previous one with escape sequences, in color
+    Terminal,
+    /// Each frame is written below the previous one as plain text, without any escape sequences
+    Plain,
+}
+
+impl Output {
+    /// Chooses plain output if plain is set, as with --plain, or if stdout is not a terminal by is_terminal, such as stdout_is_terminal, and output for the terminal otherwise
+    pub fn choose(plain: bool, is_terminal: impl FnOnce() -> bool) -> Output {
+        if plain || !is_terminal() {
+            Output::Plain
+        } else {
+            Output::Terminal
+        }
+    }
+
+    /// Appends the bytes that write a frame to out. For the terminal, they draw it over the previous frame unless it is the first, and when plain, they write it below the previous one. The whole frame is composed before any of it is written, so that it reaches the terminal in one write and does not flicker.
+    pub fn compose_frame(self, frame: &Frame, glyphs: Glyphs, first: bool, out: &mut Vec<u8>) {
+        if self == Output::Plain {
+            // A blank line before every frame but the first keeps the frames apart.
+            if !first {
+                out.push(b'\n');
+            }
+            for row in 0..frame.glyph_rows(glyphs) {
+                frame.encode_plain_glyph_row(glyphs, row, out);
+                out.push(b'\n');
+            }
+            return;
+        }
+
+        // Uses the ANSI escape sequence \x1b[{}A to move the cursor up by the height of the frame before every frame but the first. This has the effect of moving the cursor back to the top of the previous frame, which is useful for creating an animation where the frame is redrawn in the same location on the screen for each iteration of the loop. Without this code, each frame of the animation would be printed on a new line below the previous frame, causing the animation to scroll down the screen. Because the cursor is only moved up before a new frame, it is left below the last frame when the animation ends.
+        if !first {
+            out.extend_from_slice(cursor_up(frame.glyph_rows(glyphs)).as_bytes());
+        }
+
+        // Iterates over the rows of the frame, which represents the screen, and appends the bytes of each row, as encoded by encode_glyph_row with the ANSI escape sequences for any colored cells, followed by a newline. Each line is erased before its row is drawn, so nothing left over from the previous frame can show through. This has the effect of drawing the contents of the frame to the console, which represents the wireframe representation of the spinning cube.
+        for row in 0..frame.glyph_rows(glyphs) {
+            out.extend_from_slice(CLEAR_LINE.as_bytes());
+            frame.encode_glyph_row(glyphs, row, out);
+            out.push(b'\n');
+        }
+    }
+}
+
+/// Returns the width and height in characters of the biggest screen that fits in a terminal of the given number of columns and rows. The last row is left free, since the newline after the bottom row of the screen moves the cursor onto it, and a screen as tall as the terminal would scroll.
+pub fn screen_for_terminal([columns, rows]: [usize; 2]) -> [usize; 2] {
+    [columns.max(1), rows.saturating_sub(1).max(1)]
+}
+
+/// Returns the size of the screen to start with, given the size of the terminal from terminal_size: the biggest that fits in the terminal, or SCREEN_WIDTH by SCREEN_HEIGHT if its size could not be found
+pub fn initial_screen(terminal: io::Result<[usize; 2]>) -> [usize; 2] {
+    match terminal {
+        Ok(size) => screen_for_terminal(size),
+        Err(_) => [crate::SCREEN_WIDTH, crate::SCREEN_HEIGHT],
+    }
+}
+
+/// Escape sequence that erases the whole screen and moves the cursor to its top left corner
+pub const CLEAR_SCREEN : &str = "\x1b[2J\x1b[H";
+
+/// Escape sequence that hides the cursor
+pub const HIDE_CURSOR : &str = "\x1b[?25l";
+
+/// Escape sequence that shows the cursor
+pub const SHOW_CURSOR : &str = "\x1b[?25h";
+
+/// Escape sequence that erases the whole line the cursor is on
+pub const CLEAR_LINE : &str = "\x1b[2K";
+
+/// Returns the escape sequence that moves the cursor up by the given number of lines
+pub fn cursor_up(lines: usize) -> String {
+    format!("\x1b[{}A", lines)
+}
+
+/// Hides the cursor of the terminal written to by W, which is stdout unless given, for as long as it is alive. The cursor is shown again when the guard is dropped, which also happens when unwinding from a panic.
+pub struct HiddenCursor<W: Write = io::Stdout>(W);
+
+impl HiddenCursor {
+    /// Hides the cursor on stdout until the returned guard is dropped
+    pub fn new() -> Self {
+        HiddenCursor::with_writer(io::stdout())
+    }
+}
+
+impl<W: Write> HiddenCursor<W> {
+    /// Hides the cursor by writing to out until the returned guard is dropped
+    pub fn with_writer(mut out: W) -> Self {
+        emit(&mut out, HIDE_CURSOR);
+        HiddenCursor(out)
+    }
+}
+
+impl Default for HiddenCursor {
+    fn default() -> Self {
+        HiddenCursor::new()
+    }
+}
+
+impl<W: Write> Drop for HiddenCursor<W> {
+    fn drop(&mut self) {
+        emit(&mut self.0, SHOW_CURSOR);
+    }
+}
+
+/// Escape sequence that switches to the alternate screen and moves the cursor to its top left corner
+pub const ENTER_ALTERNATE_SCREEN : &str = "\x1b[?1049h\x1b[H";
+
+/// Escape sequence that switches back from the alternate screen to the main screen
+pub const LEAVE_ALTERNATE_SCREEN : &str = "\x1b[?1049l";
+
+/// Switches the terminal written to by W, which is stdout unless given, to the alternate screen for as long as it is alive, so that the animation does not scroll away what was on the terminal before. The main screen, with its previous contents, is restored when the guard is dropped, which also happens when unwinding from a panic.
+pub struct AlternateScreen<W: Write = io::Stdout>(W);
+
+impl AlternateScreen {
+    /// Switches stdout to the alternate screen until the returned guard is dropped
+    pub fn new() -> Self {
+        AlternateScreen::with_writer(io::stdout())
+    }
+}
+
+impl<W: Write> AlternateScreen<W> {
+    /// Switches to the alternate screen by writing to out until the returned guard is dropped
+    pub fn with_writer(mut out: W) -> Self {
+        emit(&mut out, ENTER_ALTERNATE_SCREEN);
+        AlternateScreen(out)
+    }
+}
+
+impl Default for AlternateScreen {
+    fn default() -> Self {
+        AlternateScreen::new()
+    }
+}
+
+impl<W: Write> Drop for AlternateScreen<W> {
+    fn drop(&mut self) {
+        emit(&mut self.0, LEAVE_ALTERNATE_SCREEN);
+    }
+}
+
+/// Escape sequence that makes the terminal report mouse buttons and movement as SGR mouse sequences
+pub const ENABLE_MOUSE : &str = "\x1b[?1000h\x1b[?1003h\x1b[?1006h";
+
+/// Escape sequence that stops the terminal from reporting the mouse
+pub const DISABLE_MOUSE : &str = "\x1b[?1006l\x1b[?1003l\x1b[?1000l";
+
+/// Makes the terminal report the mouse for as long as it is alive. Reporting is switched off again when the guard is dropped, which also happens when unwinding from a panic.
+pub struct MouseReporting(());
+
+impl MouseReporting {
+    /// Enables mouse reporting until the returned guard is dropped
+    pub fn new() -> Self {
+        emit(&mut io::stdout(), ENABLE_MOUSE);
+        MouseReporting(())
+    }
+}
+
+impl Default for MouseReporting {
+    fn default() -> Self {
+        MouseReporting::new()
+    }
+}
+
+impl Drop for MouseReporting {
+    fn drop(&mut self) {
+        emit(&mut io::stdout(), DISABLE_MOUSE);
+    }
+}
+
+/// Puts the terminal into raw mode for as long as it is alive, so that keys can be read as soon as they are pressed, without being echoed or waiting for Enter. Ctrl-C still interrupts the program and output is still processed, so newlines still return to the start of the line. The previous mode is restored when the guard is dropped, which also happens when unwinding from a panic.
+pub struct RawMode {
+    original: libc::termios,
+}
+
+impl RawMode {
+    /// Switches the terminal on stdin to raw mode until the returned guard is dropped. Fails if stdin is not a terminal.
+    pub fn new() -> io::Result<Self> {
+        let mut original = std::mem::MaybeUninit::<libc::termios>::uninit();
+        // SAFETY: tcgetattr fills in the termios struct when it succeeds.
+        let original = unsafe {
+            if libc::tcgetattr(libc::STDIN_FILENO, original.as_mut_ptr()) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            original.assume_init()
+        };
+        let mut raw = original;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+        // Reads return straight away with whatever input is available, so they never hold up the animation.
+        raw.c_cc[libc::VMIN] = 0;
+        raw.c_cc[libc::VTIME] = 0;
+        // SAFETY: raw is a valid termios struct.
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(RawMode { original })
+    }
+
+    /// Returns the input typed since the last call, without waiting for more
+    pub fn read_input(&self) -> Vec<u8> {
+        let mut input = Vec::new();
+        let mut buf = [0u8; 64];
+        loop {
+            // SAFETY: buf is valid for writes of its whole length.
+            let n = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr().cast(), buf.len()) };
+            if n <= 0 {
+                break;
+            }
+            input.extend_from_slice(&buf[..n as usize]);
+        }
+        input
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // SAFETY: original is the valid termios struct read when the guard was created.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// Writes an escape sequence to out straight away. Errors are ignored, since there is nothing useful to do if the terminal has gone away.
+fn emit(out: &mut impl Write, sequence: &str) {
+    let _ = out.write_all(sequence.as_bytes());
+    let _ = out.flush();
+}
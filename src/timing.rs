@@ -0,0 +1,22 @@
+//! Timing of the frames of the animation
+
+use std::time::Duration;
+
+/// Angle the cube has turned through after rotating for the given time at velocity radians per second
+pub fn angle_at(elapsed: Duration, velocity: f32) -> f32 {
+    elapsed.as_secs_f32() * velocity
+}
+
+/// Time to sleep after each frame to draw the given number of frames per second, or None to not sleep at all
+pub fn frame_delay(fps: u32) -> Option<Duration> {
+    (fps > 0).then(|| Duration::from_millis(1000 / fps as u64))
+}
+
+/// Text of the overlay shown with --hud, given the real time the last frame took, or None for the first frame. A frame that took no time at all by the clock has no rate to show either.
+pub fn hud_text(frame_time: Option<Duration>, frame_number: u64) -> String {
+    match frame_time {
+        Some(Duration::ZERO) => format!("-- fps  0.0 ms  frame {}", frame_number),
+        Some(frame_time) => format!("{:.1} fps  {:.1} ms  frame {}", 1.0 / frame_time.as_secs_f64(), frame_time.as_secs_f64() * 1000.0, frame_number),
+        None => format!("-- fps  -- ms  frame {}", frame_number),
+    }
+}
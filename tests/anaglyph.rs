@@ -0,0 +1,43 @@
+//! Tests of drawing the scene for red and cyan glasses
+
+use simple_rust_cube::{render_anaglyph_into, translation, Frame, Mesh, Model, RenderOptions, Vector, ANAGLYPH_BOTH, ANAGLYPH_LEFT, ANAGLYPH_RIGHT};
+
+/// A single upright triangle facing the camera, whose left edge is vertical
+fn triangle() -> Mesh {
+    Mesh {
+        vertices: vec![Vector([-1.0, -1.0, 0.0, 1.0]), Vector([-1.0, 1.0, 0.0, 1.0]), Vector([1.0, -1.0, 0.0, 1.0])],
+        faces: vec![vec![0, 1, 2]],
+    }
+}
+
+/// Columns of a row of the frame drawn in the given color
+fn columns_in(frame: &Frame, y: usize, color: u8) -> Vec<usize> {
+    (0..frame.width()).filter(|&x| frame.row(y)[x] != ' ' && frame.row_colors(y)[x] == Some(color)).collect()
+}
+
+#[test]
+fn edge_is_seen_by_each_eye_at_a_different_place() {
+    let mesh = triangle();
+    let model = Model { mesh: &mesh, model_to_world: translation(0.0, 0.0, -3.0) };
+    let mut frame = Frame::new(60, 30);
+    render_anaglyph_into(&mut frame, &[model], &RenderOptions::default(), 0.5);
+    // Halfway down, the row crosses the vertical left edge once for each eye, in the same character but at different columns. Something in front of the eyes is seen further right by the left eye than by the right one.
+    let y = 15;
+    let (left, right) = (columns_in(&frame, y, ANAGLYPH_LEFT)[0], columns_in(&frame, y, ANAGLYPH_RIGHT)[0]);
+    assert_eq!((frame.row(y)[left], frame.row(y)[right]), ('|', '|'));
+    assert!(left > right + 2, "{:?}", frame.row(y));
+    assert!(columns_in(&frame, y, ANAGLYPH_BOTH).is_empty());
+}
+
+#[test]
+fn eyes_in_the_same_place_see_the_same() {
+    let mesh = triangle();
+    let model = Model { mesh: &mesh, model_to_world: translation(0.0, 0.0, -3.0) };
+    let mut frame = Frame::new(60, 30);
+    render_anaglyph_into(&mut frame, &[model], &RenderOptions::default(), 0.0);
+    for y in 0..frame.height() {
+        assert!(columns_in(&frame, y, ANAGLYPH_LEFT).is_empty());
+        assert!(columns_in(&frame, y, ANAGLYPH_RIGHT).is_empty());
+    }
+    assert!(!columns_in(&frame, 15, ANAGLYPH_BOTH).is_empty());
+}
@@ -0,0 +1,60 @@
+//! Tests of the options the binary takes on the command line
+
+use std::process::Command;
+
+/// Runs the binary with the given arguments and returns what it wrote to stdout, which is a pipe, so the frames are plain text with a blank line between them
+fn run(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube")).args(args).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn rows_are_as_wide_as_the_screen_asked_for() {
+    let output = run(&["--width", "57", "--height", "13", "--frames", "1", "--fps", "0"]);
+    let rows: Vec<&str> = output.lines().collect();
+    assert_eq!(rows.len(), 13);
+    assert!(rows.iter().all(|row| row.chars().count() == 57));
+    assert!(rows.iter().any(|row| row.trim() != ""));
+}
+
+/// The frames the binary drew, split at the blank lines between them
+fn frames(output: &str) -> Vec<&str> {
+    output.trim_end_matches('\n').split("\n\n").collect()
+}
+
+#[test]
+fn speed_zero_draws_the_same_frame_every_time() {
+    let args = ["--width", "30", "--height", "12", "--frames", "4", "--fps", "0"];
+    let output = run(&[&args[..], &["--speed", "0"]].concat());
+    let still = frames(&output);
+    assert_eq!(still.len(), 4);
+    assert!(still.windows(2).all(|pair| pair[0] == pair[1]));
+    // At the usual speed the cube turns from one frame to the next.
+    let output = run(&args);
+    let turning = frames(&output);
+    assert!(turning.windows(2).any(|pair| pair[0] != pair[1]));
+}
+
+#[test]
+fn frames_draws_that_many_frames_and_exits() {
+    for count in [1, 3] {
+        // run checks that the binary exits successfully, and waiting for it checks that it exits at all.
+        let output = run(&["--width", "30", "--height", "10", "--frames", &count.to_string(), "--fps", "0"]);
+        let drawn = frames(&output);
+        assert_eq!(drawn.len(), count);
+        assert!(drawn.iter().all(|frame| frame.lines().count() == 10));
+    }
+}
+
+#[test]
+fn help_is_printed_only_when_asked_for_as_a_flag() {
+    let help = run(&["--width", "0", "--help"]);
+    assert!(help.contains("-h, --help"));
+    assert_eq!(run(&["-h"]), help);
+    // A -h after an option that takes a value is the value, here the name of the model file.
+    let output = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube")).args(["--model", "-h", "--frames", "1"]).output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot read -h"));
+    assert!(output.stdout.is_empty());
+}
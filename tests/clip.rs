@@ -0,0 +1,86 @@
+//! Tests of clipping lines to the screen with clip_line, and lines and polygons in camera coordinates to the near and far planes
+
+use simple_rust_cube::{approx_eq, clip_depth, clip_line, clip_polygon_depth, render_frame, CullMode, Mesh, RenderMode, RenderOptions, Vector, IDENTITY};
+
+/// Whether the coordinates and depths of two points differ by no more than rounding
+fn close(a: [f32; 3], b: [f32; 3]) -> bool {
+    a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-5)
+}
+
+#[test]
+fn line_inside_the_screen_is_unchanged() {
+    let (start, end) = ([2.0, 3.0, 0.1], [37.0, 15.0, 0.9]);
+    assert_eq!(clip_line(start, end, 40, 20), Some((start, end)));
+}
+
+#[test]
+fn line_crossing_one_edge_ends_on_it() {
+    // The screen reaches half a cell above the centers of the top row of cells.
+    let (start, end) = clip_line([10.0, 10.0, 0.0], [10.0, -10.0, 1.0], 40, 20).unwrap();
+    assert_eq!(start, [10.0, 10.0, 0.0]);
+    assert!(close(end, [10.0, -0.5, 0.525]), "{:?}", end);
+}
+
+#[test]
+fn line_crossing_two_edges_ends_on_both() {
+    let (start, end) = clip_line([-10.0, 10.0, 0.0], [50.0, 10.0, 0.6], 40, 20).unwrap();
+    assert!(close(start, [-0.5, 10.0, 0.095]), "{:?}", start);
+    assert!(close(end, [39.5, 10.0, 0.495]), "{:?}", end);
+    // A line crossing two edges that meet at a corner keeps its slope.
+    let (start, end) = clip_line([-5.0, 5.0, 0.0], [15.0, -15.0, 0.0], 40, 20).unwrap();
+    assert!(close(start, [-0.5, 0.5, 0.0]), "{:?}", start);
+    assert!(close(end, [0.5, -0.5, 0.0]), "{:?}", end);
+}
+
+#[test]
+fn line_outside_the_screen_is_left_out() {
+    assert_eq!(clip_line([-5.0, -5.0, 0.0], [50.0, -2.0, 0.0], 40, 20), None);
+    // This line runs past the top left corner of the screen, with its ends beyond different edges.
+    assert_eq!(clip_line([-10.0, 5.0, 0.0], [5.0, -10.0, 0.0], 40, 20), None);
+}
+
+#[test]
+fn line_through_the_near_plane_ends_on_it() {
+    let (near, far) = (0.5, 100.0);
+    let (start, end) = (Vector([2.0, 1.0, -4.5, 1.0]), Vector([0.0, 2.0, 0.5, 1.0]));
+    let (clipped_start, clipped_end) = clip_depth(&start, &end, near, far).unwrap();
+    assert_eq!(clipped_start, start);
+    // The line is cut a fifth of the way before its end, where it is half a unit in front of the camera.
+    assert!(approx_eq(&clipped_end, &Vector([0.4, 1.8, -0.5, 1.0]), 1e-5), "{:?}", clipped_end);
+    // It is cut at the same place whichever way it runs.
+    let (reversed_start, reversed_end) = clip_depth(&end, &start, near, far).unwrap();
+    assert!(approx_eq(&reversed_start, &clipped_end, 1e-5) && reversed_end == start);
+    // Past the far plane it is cut on the far plane, and entirely behind the camera nothing is left.
+    let (_, far_end) = clip_depth(&start, &Vector([2.0, 1.0, -200.0, 1.0]), near, far).unwrap();
+    assert!((far_end.0[2] + far).abs() < 1e-4);
+    assert_eq!(clip_depth(&Vector([0.0, 0.0, 1.0, 1.0]), &Vector([1.0, 0.0, 3.0, 1.0]), near, far), None);
+}
+
+#[test]
+fn polygon_through_the_near_plane_gets_corners_on_it() {
+    // A square lying flat, with the near half of it in front of the near plane
+    let square = vec![Vector([-1.0, 0.0, -3.0, 1.0]), Vector([-1.0, 0.0, 1.0, 1.0]), Vector([1.0, 0.0, 1.0, 1.0]), Vector([1.0, 0.0, -3.0, 1.0])];
+    let clipped = clip_polygon_depth(square, 1.0, 100.0);
+    let expected = [Vector([-1.0, 0.0, -3.0, 1.0]), Vector([-1.0, 0.0, -1.0, 1.0]), Vector([1.0, 0.0, -1.0, 1.0]), Vector([1.0, 0.0, -3.0, 1.0])];
+    assert_eq!(clipped.len(), expected.len());
+    assert!(clipped.iter().zip(&expected).all(|(a, b)| approx_eq(a, b, 1e-6)), "{:?}", clipped);
+    // Cutting off one corner of a triangle leaves a quadrilateral.
+    let triangle = vec![Vector([-1.0, 0.0, -4.0, 1.0]), Vector([0.0, 1.0, 0.0, 1.0]), Vector([1.0, 0.0, -4.0, 1.0])];
+    assert_eq!(clip_polygon_depth(triangle, 0.1, 100.0).len(), 4);
+    assert!(clip_polygon_depth(vec![Vector([0.0, 0.0, 1.0, 1.0]), Vector([1.0, 0.0, 1.0, 1.0]), Vector([0.0, 1.0, 1.0, 1.0])], 0.1, 100.0).is_empty());
+}
+
+#[test]
+fn filled_face_reaching_past_the_camera_is_drawn_where_it_is_in_view() {
+    // A floor under the camera that runs from far ahead to behind it
+    let floor = Mesh {
+        vertices: vec![Vector([-3.0, -1.0, -20.0, 1.0]), Vector([-3.0, -1.0, 5.0, 1.0]), Vector([3.0, -1.0, 5.0, 1.0]), Vector([3.0, -1.0, -20.0, 1.0])],
+        faces: vec![vec![0, 1, 2, 3]],
+    };
+    let (width, height) = (40, 20);
+    let options = RenderOptions { mode: RenderMode::Filled, cull_mode: CullMode::None, ..RenderOptions::default() };
+    let frame = render_frame(&floor, &IDENTITY, &options, width, height);
+    // The floor fills the bottom row of the screen, right under the camera, and nothing above the horizon.
+    assert!(frame.row(height - 1).iter().all(|&c| c == '#'), "{:?}", frame.row_to_string(height - 1));
+    assert!((0..height / 2).all(|y| frame.row(y).iter().all(|&c| c == ' ')));
+}
@@ -0,0 +1,47 @@
+//! Tests of drawing faces in their own colors
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use simple_rust_cube::{render_frame, translation, Mesh, RenderMode, RenderOptions, Vector, FACE_COLORS};
+
+#[test]
+fn colored_face_is_surrounded_by_its_escape_codes() {
+    // A single square facing the camera, wound clockwise as seen from the front like the faces of the cube
+    let square = Mesh {
+        vertices: vec![Vector([-1.0, -1.0, 0.0, 1.0]), Vector([-1.0, 1.0, 0.0, 1.0]), Vector([1.0, 1.0, 0.0, 1.0]), Vector([1.0, -1.0, 0.0, 1.0])],
+        faces: vec![vec![0, 1, 2, 3]],
+    };
+    let options = RenderOptions { mode: RenderMode::Filled, colors: Some(FACE_COLORS), ..RenderOptions::default() };
+    let frame = render_frame(&square, &translation(0.0, 0.0, -3.0), &options, 40, 20);
+    let row = frame.row(10);
+    let first = row.iter().position(|&c| c == '#').unwrap();
+    let last = row.iter().rposition(|&c| c == '#').unwrap();
+    assert!(row[first..=last].iter().all(|&c| c == '#'));
+    assert!(frame.row_colors(10)[first..=last].iter().all(|&color| color == Some(FACE_COLORS[0])));
+    // The color is switched on just before the first cell of the face and reset just after the last.
+    let expected = format!("{}\x1b[38;5;{}m{}\x1b[0m{}", " ".repeat(first), FACE_COLORS[0], "#".repeat(last + 1 - first), " ".repeat(39 - last));
+    assert_eq!(frame.row_to_string(10), expected);
+}
+
+#[test]
+fn colors_flag_draws_the_faces_in_color() {
+    let run = |args: &[&str]| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube"))
+            .args(["--width", "40", "--height", "16", "--script", "--mode", "filled"])
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(b"yaw 0.8\npitch 0.7\nrender\n").unwrap();
+        let output = child.wait_with_output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert!(!run(&[]).contains("\x1b[38;5;"));
+    let colored = run(&["--colors"]);
+    let used = FACE_COLORS.iter().filter(|color| colored.contains(&format!("\x1b[38;5;{}m", color))).count();
+    // Three faces of the cube can be seen, each in its own color.
+    assert!(used >= 3, "{:?}", colored);
+}
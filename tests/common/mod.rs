@@ -0,0 +1,25 @@
+//! Helpers shared by the integration tests
+
+use std::path::PathBuf;
+
+/// Compares text with the golden file of the given name, showing the lines that differ if they are not the same, or overwrites the golden file with it if UPDATE_GOLDEN is set
+pub fn assert_golden(name: &str, actual: &str) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "golden", name].iter().collect();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("cannot read {}: {} (run with UPDATE_GOLDEN=1 to create it)", path.display(), err));
+    if expected == actual {
+        return;
+    }
+    let mut diff = String::new();
+    let (expected_lines, actual_lines): (Vec<&str>, Vec<&str>) = (expected.lines().collect(), actual.lines().collect());
+    for line in 0..expected_lines.len().max(actual_lines.len()) {
+        let (expected, actual) = (expected_lines.get(line), actual_lines.get(line));
+        if expected != actual {
+            diff.push_str(&format!("line {}:\n  expected: {:?}\n  actual:   {:?}\n", line + 1, expected.unwrap_or(&""), actual.unwrap_or(&"")));
+        }
+    }
+    panic!("output differs from {}:\n{}", path.display(), diff);
+}
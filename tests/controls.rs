@@ -0,0 +1,84 @@
+//! Tests of steering the cube with the keyboard and the mouse
+
+use simple_rust_cube::controls::{key_rotation, Controls, KEY_STEP, MAX_FOV, MIN_FOV};
+use simple_rust_cube::input::{Event, Key};
+use simple_rust_cube::{perspective, project, translation, VERTICES};
+
+#[test]
+fn arrow_keys_turn_the_cube_by_one_step() {
+    assert_eq!(key_rotation(Key::Left), Some((KEY_STEP, 0.0)));
+    assert_eq!(key_rotation(Key::Right), Some((-KEY_STEP, 0.0)));
+    assert_eq!(key_rotation(Key::Up), Some((0.0, -KEY_STEP)));
+    assert_eq!(key_rotation(Key::Down), Some((0.0, KEY_STEP)));
+}
+
+#[test]
+fn other_keys_do_not_turn_the_cube() {
+    for c in ['a', 'q', ' ', 'h', 'j', 'k', 'l'] {
+        assert_eq!(key_rotation(Key::Char(c)), None);
+    }
+}
+
+#[test]
+fn space_pauses_and_resumes_the_animation() {
+    let mut controls = Controls::new(60.0, 4.0);
+    assert!(controls.spinning() && !controls.paused);
+    controls.handle_event(Event::Key(Key::Char(' ')));
+    assert!(controls.paused && !controls.spinning());
+    // Turning the cube by hand while paused leaves it paused.
+    controls.handle_event(Event::Key(Key::Left));
+    assert!(!controls.spinning());
+    controls.handle_event(Event::Key(Key::Char(' ')));
+    assert!(controls.spinning());
+    // Resuming does not switch spinning on when it was switched off with 'a'.
+    controls.handle_key(Key::Char('a'));
+    controls.handle_key(Key::Char(' '));
+    controls.handle_key(Key::Char(' '));
+    assert!(!controls.paused && !controls.spinning());
+    assert!(!controls.quit);
+}
+
+#[test]
+fn q_quits_whether_paused_or_not() {
+    for paused in [false, true] {
+        let mut controls = Controls::new(60.0, 4.0);
+        if paused {
+            controls.handle_key(Key::Char(' '));
+        }
+        controls.handle_key(Key::Char('x'));
+        assert!(!controls.quit);
+        controls.handle_key(Key::Char('q'));
+        assert!(controls.quit);
+        assert_eq!(controls.paused, paused);
+    }
+}
+
+#[test]
+fn zoom_keeps_the_field_of_view_within_its_limits() {
+    let mut controls = Controls::new(60.0, 4.0);
+    controls.zoom(1000.0);
+    assert_eq!(controls.fov, MIN_FOV);
+    controls.zoom(0.001);
+    assert_eq!(controls.fov, MAX_FOV);
+    // The keys stop at the same limits.
+    for _ in 0..100 {
+        controls.handle_key(Key::Char('+'));
+    }
+    assert_eq!(controls.fov, MIN_FOV);
+    for _ in 0..100 {
+        controls.handle_key(Key::Char('-'));
+    }
+    assert_eq!(controls.fov, MAX_FOV);
+}
+
+#[test]
+fn zooming_in_makes_the_cube_bigger() {
+    // Height on the screen, in normalized device coordinates, of the top right corner of the front face of the cube seen through the field of view of the controls
+    let size = |controls: &Controls| project(&perspective(controls.fov.to_radians(), 1.0, 0.1, 100.0), &(translation(0.0, 0.0, -controls.distance) * VERTICES[7])).unwrap().0[1];
+    let mut controls = Controls::new(60.0, 4.0);
+    let before = size(&controls);
+    controls.zoom(2.0);
+    assert!(controls.fov < 60.0);
+    // Zooming in by a factor makes things that many times as big.
+    assert!((size(&controls) - 2.0 * before).abs() < 1e-4, "{} and {}", before, size(&controls));
+}
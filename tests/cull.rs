@@ -0,0 +1,106 @@
+//! Tests of telling which faces face away from the viewer, comparing the normals of whole faces with the winding of their first three vertices on the screen
+// cull is deprecated, but it is still what the winding on the screen is checked with here.
+#![allow(deprecated)]
+
+use simple_rust_cube::export::frame_to_svg;
+use simple_rust_cube::{approx_eq, cull, face_normal, is_back_face, perspective, project, render_frame, rotation_x, rotation_y, translation, viewport, CullMode, Matrix, Mesh, Model, Projection, RenderMode, RenderOptions, Vector};
+
+/// Screen coordinates of the first three vertices of a face as seen through the usual perspective, like the old screen-space culling used
+fn first_three_on_screen(vertices: &[Vector]) -> [[f32; 2]; 3] {
+    let projection = perspective(std::f32::consts::FRAC_PI_2, 2.0 * 0.5, 0.1, 100.0);
+    [0, 1, 2].map(|i| {
+        let [x, y, _] = viewport(&project(&projection, &vertices[i]).unwrap(), 40, 40);
+        [x, y]
+    })
+}
+
+/// Moves the vertices of a face in front of the camera after turning them
+fn place(vertices: &[Vector], model_to_world: &Matrix) -> Vec<Vector> {
+    vertices.iter().map(|v| *model_to_world * *v).collect()
+}
+
+/// Centroid of the vertices of a face
+fn centroid(vertices: &[Vector]) -> Vector {
+    let n = vertices.len() as f32;
+    Vector([0, 1, 2, 3].map(|axis| vertices.iter().map(|v| v.0[axis]).sum::<f32>() / n))
+}
+
+#[test]
+fn both_methods_agree_on_a_tilted_flat_face() {
+    // A square wound clockwise as seen from the front, like the faces of the cube
+    let square = [Vector([-1.0, -1.0, 0.0, 1.0]), Vector([-1.0, 1.0, 0.0, 1.0]), Vector([1.0, 1.0, 0.0, 1.0]), Vector([1.0, -1.0, 0.0, 1.0])];
+    let face = [0, 1, 2, 3];
+    for angle in [-1.2, -0.6, 0.3, 0.9, 2.0, 2.8] {
+        let vertices = place(&square, &(translation(0.0, 0.0, -4.0) * rotation_x(angle)));
+        let [p0, p1, p2] = first_three_on_screen(&vertices);
+        let by_normal = is_back_face(&face_normal(&face, &vertices), &centroid(&vertices), Projection::Perspective);
+        assert_eq!(by_normal, cull(p0, p1, p2), "at {}", angle);
+    }
+}
+
+#[test]
+fn concave_face_is_seen_from_the_front_by_its_normal() {
+    // A chevron wound clockwise as seen from the front, whose second corner points inwards, so that its first three corners wind the other way
+    let chevron = [Vector([-1.0, 1.0, 0.0, 1.0]), Vector([0.0, 0.0, 0.0, 1.0]), Vector([1.0, 1.0, 0.0, 1.0]), Vector([0.0, -1.0, 0.0, 1.0])];
+    let face = [0, 1, 2, 3];
+    let model_to_world = translation(0.0, 0.0, -4.0) * rotation_x(0.3);
+    let vertices = place(&chevron, &model_to_world);
+    let [p0, p1, p2] = first_three_on_screen(&vertices);
+    // The winding of the first three corners says the face is turned away, which is wrong.
+    assert!(cull(p0, p1, p2));
+    let normal = face_normal(&face, &vertices);
+    assert!(normal.0[2] > 0.9);
+    assert!(!is_back_face(&normal, &centroid(&vertices), Projection::Perspective));
+
+    let mesh = Mesh { vertices: chevron.to_vec(), faces: vec![face.to_vec()] };
+    let options = RenderOptions { mode: RenderMode::Filled, ..RenderOptions::default() };
+    let frame = render_frame(&mesh, &model_to_world, &options, 40, 20);
+    assert!((0..frame.height()).any(|y| frame.row(y).contains(&'#')));
+}
+
+#[test]
+fn normal_of_a_bent_face_is_the_average_direction() {
+    // A quad with one corner lifted towards the viewer still faces the viewer as a whole.
+    let bent = [Vector([-1.0, -1.0, 0.0, 1.0]), Vector([-1.0, 1.0, 0.0, 1.0]), Vector([1.0, 1.0, 0.6, 1.0]), Vector([1.0, -1.0, 0.0, 1.0])];
+    let normal = face_normal(&[0, 1, 2, 3], &bent);
+    assert!(normal.0[2] > 0.9);
+    assert!(normal.0[0] < 0.0 && normal.0[1] < 0.0);
+}
+
+#[test]
+fn normals_of_the_cube_point_out_of_it() {
+    let cube = Mesh::cube();
+    let plus_x = cube.faces.iter().find(|face| face.iter().all(|&i| cube.vertices[i].0[0] == 1.0)).unwrap();
+    let normal = face_normal(plus_x, &cube.vertices);
+    assert!(approx_eq(&normal, &Vector([1.0, 0.0, 0.0, 0.0]), 1e-6), "{:?}", normal);
+    // Every face of the cube is centered on its axis, so its normal points the same way as its centroid.
+    for face in &cube.faces {
+        let vertices: Vec<Vector> = face.iter().map(|&i| cube.vertices[i]).collect();
+        let center = centroid(&vertices);
+        assert!(approx_eq(&face_normal(face, &cube.vertices), &Vector([center.0[0], center.0[1], center.0[2], 0.0]), 1e-6));
+    }
+}
+
+/// Number of edges drawn of the cube with the given faces left out, counted as the lines of its wireframe as an SVG document. The cube is turned so that three of its faces, which meet at a corner, face the viewer.
+fn edges_drawn(cull_mode: CullMode) -> usize {
+    let mesh = Mesh::cube();
+    let models = [Model { mesh: &mesh, model_to_world: translation(0.0, 0.0, -4.0) * rotation_x(0.6) * rotation_y(0.8) }];
+    let options = RenderOptions { cull_mode, ..RenderOptions::default() };
+    frame_to_svg(&models, &options, 40, 20, [8, 16]).matches("<line ").count()
+}
+
+#[test]
+fn back_culling_leaves_the_three_faces_towards_the_viewer() {
+    // Three faces have four edges each, but each of them shares one edge with each of the other two.
+    assert_eq!(edges_drawn(CullMode::Back), 9);
+}
+
+#[test]
+fn front_culling_leaves_the_three_faces_away_from_the_viewer() {
+    assert_eq!(edges_drawn(CullMode::Front), 9);
+}
+
+#[test]
+fn no_culling_draws_every_edge() {
+    assert_eq!(edges_drawn(CullMode::None), 12);
+}
@@ -0,0 +1,38 @@
+//! Tests of how far the camera is from the cube
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Width in columns of the cube as the binary draws it with the camera distance away from it
+fn drawn_width(distance: &str) -> usize {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube"))
+        .args(["--width", "40", "--height", "16", "--script", "--distance", distance])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"yaw 0.3\nrender\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    // A script only writes the frame, every line of which starts by erasing the line, so the rest of each line is what was drawn.
+    let columns = text.lines().flat_map(|line| {
+        let line = line.strip_prefix("\x1b[2K").unwrap();
+        line.char_indices().filter(|&(_, c)| c != ' ').map(|(i, _)| i).collect::<Vec<_>>()
+    });
+    let (low, high) = columns.fold((usize::MAX, 0), |(low, high), column| (low.min(column), high.max(column)));
+    high + 1 - low
+}
+
+#[test]
+fn farther_camera_shrinks_the_cube() {
+    let widths: Vec<usize> = ["2.5", "4", "8"].iter().map(|distance| drawn_width(distance)).collect();
+    assert!(widths[0] > widths[1] && widths[1] > widths[2], "{:?}", widths);
+}
+
+#[test]
+fn distance_must_keep_the_camera_outside_the_cube() {
+    let output = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube")).args(["--distance", "1"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("the distance must be from 2 to 50"));
+}
@@ -0,0 +1,89 @@
+//! Tests of saving frames as images
+
+use std::time::Duration;
+
+use simple_rust_cube::export::{color_to_rgb, frame_to_ppm, frame_to_svg, GifExport};
+use simple_rust_cube::{project, projection_matrix, translation, viewport, Frame, Mesh, Model, RenderOptions, Vector};
+
+#[test]
+fn ppm_is_as_big_as_the_cells_it_is_made_of() {
+    let mut frame = Frame::new(3, 2);
+    frame.plot(1, 0, 0.5, '#', Some(196));
+    frame.plot(2, 1, 0.5, '#', None);
+    let ppm = frame_to_ppm(&frame, [4, 8]);
+    let header = b"P6\n12 16\n255\n";
+    assert_eq!(&ppm[..header.len()], header);
+    // Three bytes for each of the 12 by 16 pixels, and nothing after them
+    let pixels = &ppm[header.len()..];
+    assert_eq!(pixels.len(), 12 * 16 * 3);
+    let pixel = |x: usize, y: usize| [0, 1, 2].map(|c| pixels[(y * 12 + x) * 3 + c]);
+    // Every pixel of a cell is in its color, white if it has none and black if it is empty.
+    for y in 0..8 {
+        assert!((0..4).all(|x| pixel(x, y) == [0, 0, 0] && pixel(4 + x, y) == color_to_rgb(196)));
+    }
+    assert_eq!(pixel(8, 8), [255, 255, 255]);
+    assert_eq!(pixel(11, 15), [255, 255, 255]);
+    assert_eq!(pixel(7, 8), [0, 0, 0]);
+}
+
+#[test]
+fn gif_holds_every_frame_at_the_size_of_its_cells() {
+    let mut gif = GifExport::new(Vec::new(), [5, 3], [4, 8], Duration::from_millis(50)).unwrap();
+    for i in 0..4 {
+        let mut frame = Frame::new(5, 3);
+        frame.plot(i, 1, 0.5, '#', Some(46));
+        gif.add_frame(&frame).unwrap();
+    }
+    let bytes = gif.finish().unwrap();
+    let mut decoder = gif::DecodeOptions::new().read_info(&bytes[..]).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (20, 24));
+    let mut frames = 0;
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!((frame.width, frame.height), (20, 24));
+        // The delay is in hundredths of a second.
+        assert_eq!(frame.delay, 5);
+        frames += 1;
+    }
+    assert_eq!(frames, 4);
+}
+
+/// The ends of the lines of an SVG document, in pixels
+fn svg_lines(svg: &str) -> Vec<[f32; 4]> {
+    svg.lines()
+        .filter(|line| line.starts_with("<line "))
+        .map(|line| {
+            ["x1", "y1", "x2", "y2"].map(|name| {
+                let value = &line[line.find(&format!(" {}=\"", name)).unwrap() + name.len() + 3..];
+                value[..value.find('"').unwrap()].parse().unwrap()
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn svg_of_one_face_has_a_line_along_each_of_its_edges() {
+    // A single square facing the camera, wound clockwise as seen from the front like the faces of the cube
+    let square = Mesh {
+        vertices: vec![Vector([-1.0, -1.0, 0.0, 1.0]), Vector([-1.0, 1.0, 0.0, 1.0]), Vector([1.0, 1.0, 0.0, 1.0]), Vector([1.0, -1.0, 0.0, 1.0])],
+        faces: vec![vec![0, 1, 2, 3]],
+    };
+    let model_to_world = translation(0.0, 0.0, -3.0);
+    let options = RenderOptions::default();
+    let (width, height, cell) = (40, 20, [8.0, 16.0]);
+    let svg = frame_to_svg(&[Model { mesh: &square, model_to_world }], &options, width, height, [8, 16]);
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"320\" height=\"320\""), "{}", svg);
+    // Each corner is where its projection lands on the screen, in the middle of its cell.
+    let projection = projection_matrix(&options, width, height);
+    let corners: Vec<[f32; 2]> = square.vertices.iter().map(|v| {
+        let ndc = project(&projection, &(model_to_world * *v)).unwrap();
+        let [x, y, _] = viewport(&ndc, width, height);
+        [(x + 0.5) * cell[0], (y + 0.5) * cell[1]]
+    }).collect();
+    let lines = svg_lines(&svg);
+    assert_eq!(lines.len(), 4);
+    for i in 0..4 {
+        let [a, b] = [corners[i], corners[(i + 1) % 4]];
+        let along = |[x1, y1, x2, y2]: [f32; 4], [p, q]: [[f32; 2]; 2]| [x1 - p[0], y1 - p[1], x2 - q[0], y2 - q[1]].iter().all(|d| d.abs() < 1e-3);
+        assert!(lines.iter().any(|&line| along(line, [a, b]) || along(line, [b, a])), "no line from {:?} to {:?} in {:?}", a, b, lines);
+    }
+}
@@ -0,0 +1,115 @@
+//! Tests of drawing into a frame buffer that is used again for more than one frame
+
+use simple_rust_cube::{frame_to_string, render_frame, render_scene, render_scene_into, rotation_y, translation, Frame, Mesh, Model, RenderOptions, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+#[test]
+fn clear_empties_every_cell() {
+    let mut frame = Frame::new(6, 3);
+    frame.plot(1, 1, 0.5, '#', Some(196));
+    frame.write_text(0, 2, "text");
+    frame.clear();
+    for y in 0..frame.height() {
+        assert!(frame.row(y).iter().all(|&c| c == ' '));
+        assert!(frame.row_colors(y).iter().all(Option::is_none));
+    }
+    // The depth of the cleared cell must be reset as well, or this would be hidden behind what was drawn there before.
+    frame.plot(1, 1, 0.9, '*', None);
+    assert_eq!(frame.row(1)[1], '*');
+}
+
+#[test]
+fn rendering_again_leaves_nothing_of_the_previous_frame() {
+    let mesh = Mesh::cube();
+    let options = RenderOptions::default();
+    let first = Model { mesh: &mesh, model_to_world: translation(-1.0, 0.0, -4.0) };
+    let second = Model { mesh: &mesh, model_to_world: translation(1.0, 0.0, -4.0) * rotation_y(0.5) };
+    let mut frame = Frame::new(40, 20);
+    render_scene_into(&mut frame, &[first], &options);
+    render_scene_into(&mut frame, &[second], &options);
+    let fresh = render_scene(&[second], &options, 40, 20);
+    for y in 0..frame.height() {
+        assert_eq!(frame.row(y), fresh.row(y));
+        assert_eq!(frame.row_colors(y), fresh.row_colors(y));
+    }
+}
+
+#[test]
+fn empty_cells_hold_the_background() {
+    let mesh = Mesh::cube();
+    let options = RenderOptions { background: '.', ..RenderOptions::default() };
+    let model_to_world = translation(0.0, 0.0, -4.0) * rotation_y(0.5);
+    let frame = render_scene(&[Model { mesh: &mesh, model_to_world }], &options, 40, 20);
+    let plain = render_scene(&[Model { mesh: &mesh, model_to_world }], &RenderOptions::default(), 40, 20);
+    for y in 0..frame.height() {
+        for (&cell, &plain_cell) in frame.row(y).iter().zip(plain.row(y)) {
+            // Only the cells left empty change.
+            assert_eq!(cell, if plain_cell == ' ' { '.' } else { plain_cell });
+        }
+    }
+}
+
+#[test]
+fn resize_changes_the_size_and_empties_the_frame() {
+    let mut frame = Frame::new(10, 4);
+    frame.set_background('.');
+    frame.plot(2, 2, 0.5, '#', Some(196));
+    frame.resize(6, 8);
+    assert_eq!((frame.width(), frame.height()), (6, 8));
+    for y in 0..frame.height() {
+        assert_eq!(frame.row(y).len(), 6);
+        assert!(frame.row(y).iter().all(|&c| c == '.'));
+        assert!(frame.row_colors(y).iter().all(Option::is_none));
+    }
+    frame.plot(2, 2, 0.9, '*', None);
+    assert_eq!(frame.row(2)[2], '*');
+}
+
+#[test]
+fn cube_stays_centered_after_resize() {
+    let mesh = Mesh::cube();
+    let model = Model { mesh: &mesh, model_to_world: translation(0.0, 0.0, -4.0) };
+    let options = RenderOptions::default();
+    let mut frame = Frame::new(40, 20);
+    render_scene_into(&mut frame, &[model], &options);
+    frame.resize(60, 30);
+    render_scene_into(&mut frame, &[model], &options);
+    let fresh = render_scene(&[model], &options, 60, 30);
+    for y in 0..frame.height() {
+        assert_eq!(frame.row(y), fresh.row(y));
+    }
+    // The cube is drawn around the middle of the new frame.
+    let lit: Vec<(usize, usize)> = (0..30).flat_map(|y| (0..60).map(move |x| (x, y))).filter(|&(x, y)| frame.row(y)[x] != ' ').collect();
+    let (min_x, max_x) = (lit.iter().map(|p| p.0).min().unwrap(), lit.iter().map(|p| p.0).max().unwrap());
+    let (min_y, max_y) = (lit.iter().map(|p| p.1).min().unwrap(), lit.iter().map(|p| p.1).max().unwrap());
+    assert!((min_x + max_x).abs_diff(59) <= 1);
+    assert!((min_y + max_y).abs_diff(29) <= 1);
+}
+
+#[test]
+fn blit_copies_a_frame_and_cuts_it_off_at_the_edges() {
+    let mut small = Frame::new(3, 2);
+    small.plot(0, 0, 0.5, 'a', Some(196));
+    small.plot(2, 1, 0.5, 'b', None);
+    let mut frame = Frame::new(4, 3);
+    frame.blit(2, 1, &small);
+    assert_eq!(frame.row(1), &[' ', ' ', 'a', ' ']);
+    assert_eq!(frame.row_colors(1)[2], Some(196));
+    assert_eq!(frame.row(2), &[' '; 4]);
+    // The depth is copied too, so farther things drawn afterwards stay hidden.
+    frame.plot(2, 1, 0.9, 'c', None);
+    assert_eq!(frame.row(1)[2], 'a');
+}
+
+#[test]
+fn frame_to_string_puts_each_row_on_its_own_line() {
+    let frame = render_frame(&Mesh::cube(), &(translation(0.0, 0.0, -4.0) * rotation_y(0.5)), &RenderOptions::default(), SCREEN_WIDTH, SCREEN_HEIGHT);
+    let text = frame_to_string(&frame);
+    // Every row ends in a newline, the last one too, and there is nothing between them.
+    assert!(text.ends_with('\n') && !text.ends_with("\n\n"));
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), SCREEN_HEIGHT);
+    for (y, line) in lines.iter().enumerate() {
+        assert_eq!(line.chars().count(), SCREEN_WIDTH);
+        assert_eq!(*line, frame.row_to_string(y));
+    }
+}
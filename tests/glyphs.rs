@@ -0,0 +1,59 @@
+//! Tests of showing the cells of a frame as half blocks, Braille patterns or box-drawing characters
+
+use simple_rust_cube::{braille, draw_line, half_block, Charset, Frame, Glyphs};
+
+/// The characters the frame is shown as with the given glyphs, one string per row of characters, without escape sequences
+fn glyph_rows(frame: &Frame, glyphs: Glyphs) -> Vec<String> {
+    (0..frame.glyph_rows(glyphs))
+        .map(|row| {
+            let mut out = Vec::new();
+            frame.encode_plain_glyph_row(glyphs, row, &mut out);
+            String::from_utf8(out).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn each_pair_of_cells_is_one_half_block() {
+    assert_eq!(half_block(false, false), ' ');
+    assert_eq!(half_block(true, false), '\u{2580}');
+    assert_eq!(half_block(false, true), '\u{2584}');
+    assert_eq!(half_block(true, true), '\u{2588}');
+    // A frame two cells tall shows as one row of characters, the top cell of each column over the bottom one.
+    let mut frame = Frame::new(4, 2);
+    for (x, y) in [(0, 0), (0, 1), (1, 0), (2, 1)] {
+        frame.plot(x, y, 0.5, '#', None);
+    }
+    assert_eq!(glyph_rows(&frame, Glyphs::HalfBlocks), ["\u{2588}\u{2580}\u{2584} "]);
+}
+
+#[test]
+fn dots_are_numbered_like_braille() {
+    let none = [[false; 2]; 4];
+    assert_eq!(braille(none), '\u{2800}');
+    assert_eq!(braille([[true; 2]; 4]), '\u{28ff}');
+    // Dots 1, 2, 3 and 7 run down the left column, and 4, 5, 6 and 8 down the right.
+    assert_eq!(braille([[true, false]; 4]), '\u{2847}');
+    assert_eq!(braille([[false, true]; 4]), '\u{28b8}');
+    // Dots 1 and 4 along the top, and dot 8 in the bottom right corner
+    assert_eq!(braille([[true, true], [false; 2], [false; 2], [false, true]]), '\u{2889}');
+    // A frame shows each block of cells two across and four down as one pattern.
+    let mut frame = Frame::new(4, 4);
+    for (x, y) in [(0, 0), (1, 0), (1, 3), (2, 1)] {
+        frame.plot(x, y, 0.5, '#', None);
+    }
+    assert_eq!(glyph_rows(&frame, Glyphs::Braille), ["\u{2889}\u{2802}"]);
+}
+
+#[test]
+fn horizontal_edge_is_a_box_drawing_line() {
+    let mut frame = Frame::new(8, 1);
+    draw_line(&mut frame, [1.0, 0.0, 0.0], [6.0, 0.0, 0.0], &Charset::default(), None);
+    assert_eq!(glyph_rows(&frame, Glyphs::BoxDrawing), [" \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500} "]);
+    // The edges of a rectangle join up at its corners.
+    let mut frame = Frame::new(6, 4);
+    for (start, end) in [([0.0, 0.0], [5.0, 0.0]), ([0.0, 3.0], [5.0, 3.0]), ([0.0, 1.0], [0.0, 2.0]), ([5.0, 1.0], [5.0, 2.0])] {
+        draw_line(&mut frame, [start[0], start[1], 0.0], [end[0], end[1], 0.0], &Charset::default(), None);
+    }
+    assert_eq!(glyph_rows(&frame, Glyphs::BoxDrawing), ["\u{250c}\u{2500}\u{2500}\u{2500}\u{2500}\u{2510}", "\u{2502}    \u{2502}", "\u{2502}    \u{2502}", "\u{2514}\u{2500}\u{2500}\u{2500}\u{2500}\u{2518}"]);
+}
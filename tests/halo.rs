@@ -0,0 +1,53 @@
+//! Tests of which of two crossing edges is seen where they cross, and of breaking the one behind
+
+use simple_rust_cube::{draw_line, render_frame, Charset, CullMode, Frame, Mesh, Projection, RenderOptions, Vector, HALO_WIDTH, IDENTITY};
+
+#[test]
+fn near_edge_owns_the_crossing_cell_whichever_is_drawn_first() {
+    let charset = Charset::default();
+    let near = ([10.0, 0.0, 0.2], [10.0, 10.0, 0.2]);
+    let far = ([0.0, 5.0, 0.8], [20.0, 5.0, 0.8]);
+    for order in [[near, far], [far, near]] {
+        let mut frame = Frame::new(21, 11);
+        for (start, end) in order {
+            draw_line(&mut frame, start, end, &charset, None);
+        }
+        assert_eq!(frame.row(5)[10], '|');
+        assert_eq!(frame.row(5)[9], '-');
+    }
+}
+
+/// Column of the cell that the edges of render_crossing cross in
+const CROSSING : usize = 20;
+
+/// Renders a horizontal edge behind a vertical one, looking straight at them, and returns the row they cross in
+fn render_crossing(halo: bool) -> String {
+    let mesh = Mesh {
+        vertices: vec![
+            Vector([-2.0, 0.0, -4.0, 1.0]),
+            Vector([2.0, 0.0, -4.0, 1.0]),
+            Vector([0.0, -2.0, -3.0, 1.0]),
+            Vector([0.0, 2.0, -3.0, 1.0]),
+        ],
+        faces: vec![vec![0, 1], vec![2, 3]],
+    };
+    let options = RenderOptions { projection: Projection::Orthographic, cull_mode: CullMode::None, halo, ..RenderOptions::default() };
+    let frame = render_frame(&mesh, &IDENTITY, &options, 40, 20);
+    frame.row(10).iter().collect()
+}
+
+#[test]
+fn halo_breaks_the_far_edge_either_side_of_the_near_one() {
+    let (row, x) = (render_crossing(true), CROSSING);
+    let cells: Vec<char> = row.chars().collect();
+    assert_eq!(cells[x], '|');
+    for offset in 1..=HALO_WIDTH {
+        assert_eq!((cells[x - offset], cells[x + offset]), (' ', ' '), "{:?}", row);
+    }
+    assert_eq!((cells[x - HALO_WIDTH - 1], cells[x + HALO_WIDTH + 1]), ('-', '-'), "{:?}", row);
+}
+
+#[test]
+fn without_halo_the_far_edge_runs_up_to_the_near_one() {
+    assert_eq!(&render_crossing(false)[CROSSING - 2..=CROSSING + 2], "--|--");
+}
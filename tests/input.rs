@@ -0,0 +1,33 @@
+//! Tests of parsing what the terminal sends for keys and the mouse
+
+use simple_rust_cube::input::{parse_input, Event, Key, MouseEvent, MouseKind};
+
+/// The mouse event of the given kind at a column and row
+fn mouse(kind: MouseKind, column: u16, row: u16) -> Event {
+    Event::Mouse(MouseEvent { kind, column, row })
+}
+
+#[test]
+fn left_button_press_is_parsed_with_its_cell() {
+    assert_eq!(parse_input(b"\x1b[<0;20;10M"), [mouse(MouseKind::Press, 20, 10)]);
+}
+
+#[test]
+fn drag_is_followed_through_to_the_release() {
+    // Moving with the left button held down adds 32 to the button code, and the release ends in m.
+    let events = parse_input(b"\x1b[<0;20;10M\x1b[<32;22;11M\x1b[<0;22;11m");
+    assert_eq!(events, [mouse(MouseKind::Press, 20, 10), mouse(MouseKind::Drag, 22, 11), mouse(MouseKind::Release, 22, 11)]);
+    // Moving with no button held down is not a drag.
+    assert_eq!(parse_input(b"\x1b[<35;5;6M"), [mouse(MouseKind::Move, 5, 6)]);
+}
+
+#[test]
+fn mouse_sequences_mixed_with_keys_keep_their_order() {
+    assert_eq!(parse_input(b"a\x1b[<0;1;2Mq\x1b[A"), [Event::Key(Key::Char('a')), mouse(MouseKind::Press, 1, 2), Event::Key(Key::Char('q')), Event::Key(Key::Up)]);
+}
+
+#[test]
+fn broken_mouse_sequences_are_skipped() {
+    assert_eq!(parse_input(b"\x1b[<0;20M"), []);
+    assert_eq!(parse_input(b"\x1b[<0;20;70000M"), []);
+}
@@ -0,0 +1,74 @@
+//! Tests that Ctrl-C ends the animation with the terminal restored, by running the binary in a pseudo-terminal
+
+use std::fs::File;
+use std::io::Read;
+use std::os::fd::{FromRawFd, OwnedFd};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use simple_rust_cube::term::{DISABLE_MOUSE, ENABLE_MOUSE, ENTER_ALTERNATE_SCREEN, HIDE_CURSOR, INTERRUPTED_STATUS, LEAVE_ALTERNATE_SCREEN, SHOW_CURSOR};
+
+/// Opens a pseudo-terminal, returning its controlling side and the terminal side a program can run in
+fn open_pty() -> (File, OwnedFd) {
+    let (mut controller, mut terminal) = (0, 0);
+    // SAFETY: openpty fills in the two file descriptors when it succeeds, and the null pointers leave the name, settings and size as they are.
+    let result = unsafe { libc::openpty(&mut controller, &mut terminal, std::ptr::null_mut(), std::ptr::null(), std::ptr::null()) };
+    assert_eq!(result, 0, "cannot open a pseudo-terminal");
+    // SAFETY: both file descriptors were just opened and nothing else owns them.
+    unsafe { (File::from_raw_fd(controller), OwnedFd::from_raw_fd(terminal)) }
+}
+
+/// Whether echoing is switched on for the terminal, which raw mode switches off
+fn echoes(terminal: &OwnedFd) -> bool {
+    use std::os::fd::AsRawFd;
+    let mut settings = std::mem::MaybeUninit::<libc::termios>::uninit();
+    // SAFETY: tcgetattr fills in the termios struct when it succeeds.
+    let settings = unsafe {
+        assert_eq!(libc::tcgetattr(terminal.as_raw_fd(), settings.as_mut_ptr()), 0);
+        settings.assume_init()
+    };
+    settings.c_lflag & libc::ECHO != 0
+}
+
+#[test]
+fn ctrl_c_restores_the_terminal() {
+    let (mut controller, terminal) = open_pty();
+    assert!(echoes(&terminal));
+    let mut child = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube"))
+        .args(["--width", "20", "--height", "8", "--fps", "50"])
+        .stdin(Stdio::from(terminal.try_clone().unwrap()))
+        .stdout(Stdio::from(terminal.try_clone().unwrap()))
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    // Everything the program writes is read on another thread, so that it never blocks on a full terminal.
+    let reader = std::thread::spawn(move || {
+        let mut output = Vec::new();
+        let mut buf = [0; 4096];
+        // Reading fails once the program has exited and nothing else has the terminal open.
+        while let Ok(n @ 1..) = controller.read(&mut buf) {
+            output.extend_from_slice(&buf[..n]);
+        }
+        output
+    });
+    std::thread::sleep(Duration::from_millis(500));
+    // SAFETY: kill only sends a signal to the child.
+    unsafe {
+        libc::kill(child.id() as libc::pid_t, libc::SIGINT);
+    }
+    let status = child.wait().unwrap();
+    assert!(echoes(&terminal), "the terminal was left in raw mode");
+    drop(terminal);
+    let output = String::from_utf8_lossy(&reader.join().unwrap()).into_owned();
+
+    assert_eq!(status.code(), Some(INTERRUPTED_STATUS));
+    for (setup, restore) in [(ENTER_ALTERNATE_SCREEN, LEAVE_ALTERNATE_SCREEN), (HIDE_CURSOR, SHOW_CURSOR), (ENABLE_MOUSE, DISABLE_MOUSE)] {
+        let set_up = output.find(setup).unwrap_or_else(|| panic!("{:?} was never written", setup));
+        let restored = output.rfind(restore).unwrap_or_else(|| panic!("{:?} was never written", restore));
+        assert!(restored > set_up);
+    }
+    // The mouse is let go of first and the main screen comes back last, after the last frame.
+    assert!(output.rfind(DISABLE_MOUSE) < output.rfind(SHOW_CURSOR));
+    assert!(output.ends_with(LEAVE_ALTERNATE_SCREEN));
+}
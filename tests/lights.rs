@@ -0,0 +1,61 @@
+//! Tests of lighting faces with more than one light
+
+use simple_rust_cube::{diffuse_lighting, phong_lighting, render_frame, rotation_y, translation, Light, Mesh, RenderMode, RenderOptions, Vector, AMBIENT};
+
+/// A face turned towards the viewer, between the two lights
+const NORMAL : Vector = Vector([0.0, 0.0, 1.0, 0.0]);
+
+/// Two lights from either side of the face
+fn lights() -> [Light; 2] {
+    [Light::new(Vector([1.0, 0.0, 1.0, 0.0])), Light::new(Vector([-1.0, 0.0, 1.0, 0.0]))]
+}
+
+#[test]
+fn two_lights_are_brighter_than_either_alone() {
+    let [left, right] = lights();
+    let both = diffuse_lighting(&NORMAL, &[left, right]);
+    assert!(both > diffuse_lighting(&NORMAL, &[left]));
+    assert!(both > diffuse_lighting(&NORMAL, &[right]));
+    assert!((both - 2.0 * diffuse_lighting(&NORMAL, &[left])).abs() < 1e-6);
+
+    let phong_both = phong_lighting(&NORMAL, &[left, right], &NORMAL, 16.0);
+    assert!(phong_both > phong_lighting(&NORMAL, &[left], &NORMAL, 16.0));
+    assert!(phong_both > phong_lighting(&NORMAL, &[right], &NORMAL, 16.0));
+}
+
+#[test]
+fn light_is_scaled_by_its_intensity() {
+    let full = Light::new(Vector([0.0, 0.0, 1.0, 0.0]));
+    let half = Light { intensity: 0.5, ..full };
+    assert_eq!(diffuse_lighting(&NORMAL, &[full]), 1.0);
+    assert_eq!(diffuse_lighting(&NORMAL, &[half]), 0.5);
+    assert_eq!(phong_lighting(&NORMAL, &[], &NORMAL, 16.0), AMBIENT);
+}
+
+#[test]
+fn lights_behind_the_face_add_nothing() {
+    let [left, _] = lights();
+    let behind = Light::new(Vector([0.0, 0.0, -1.0, 0.0]));
+    assert_eq!(diffuse_lighting(&NORMAL, &[left, behind]), diffuse_lighting(&NORMAL, &[left]));
+}
+
+#[test]
+fn parse_lights() {
+    assert_eq!("-1,1,1".parse(), Ok(Light::new(Vector([-1.0, 1.0, 1.0, 0.0]))));
+    assert_eq!("0, 1, 0, 0.5".parse(), Ok(Light { direction: Vector([0.0, 1.0, 0.0, 0.0]), intensity: 0.5 }));
+    assert!("1,2".parse::<Light>().is_err());
+    assert!("a,b,c".parse::<Light>().is_err());
+}
+
+#[test]
+fn shaded_mode_uses_every_light() {
+    let model_to_world = translation(0.0, 0.0, -4.0) * rotation_y(0.6);
+    let render = |lights: Vec<Light>| {
+        let options = RenderOptions { mode: RenderMode::Shaded, lights, ..RenderOptions::default() };
+        let frame = render_frame(&Mesh::cube(), &model_to_world, &options, 60, 30);
+        (0..frame.height()).flat_map(|y| frame.row(y).to_vec()).collect::<String>()
+    };
+    let [left, right] = lights();
+    assert_ne!(render(vec![left, right]), render(vec![left]));
+    assert_ne!(render(vec![left, right]), render(vec![right]));
+}
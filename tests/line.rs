@@ -0,0 +1,81 @@
+//! Tests of drawing lines into a frame buffer
+
+use simple_rust_cube::{draw_depth_cued_line, draw_line, line_glyph, Charset, Frame, DEPTH_CUE};
+
+/// The cells of a frame that something has been drawn into, row by row
+fn drawn_cells(frame: &Frame) -> Vec<(usize, usize)> {
+    (0..frame.height()).flat_map(|y| (0..frame.width()).map(move |x| (x, y))).filter(|&(x, y)| frame.row(y)[x] != ' ').collect()
+}
+
+#[test]
+fn line_across_the_screen_has_no_gaps() {
+    let mut frame = Frame::new(80, 40);
+    draw_line(&mut frame, [0.0, 0.0, 0.5], [79.0, 39.0, 0.5], &Charset::default(), None);
+    let mut cells = drawn_cells(&frame);
+    // The line is wider than it is tall, so it has exactly one cell in every column.
+    cells.sort();
+    assert_eq!(cells.len(), 80);
+    assert_eq!(cells.first(), Some(&(0, 0)));
+    assert_eq!(cells.last(), Some(&(79, 39)));
+    for pair in cells.windows(2) {
+        let [(x0, y0), (x1, y1)] = [pair[0], pair[1]];
+        assert_eq!(x1, x0 + 1);
+        // Each cell touches the next one, along a side or at a corner.
+        assert!(y1 == y0 || y1 == y0 + 1, "gap between {:?} and {:?}", pair[0], pair[1]);
+    }
+}
+
+#[test]
+fn line_leaving_the_screen_is_cut_off() {
+    let mut frame = Frame::new(80, 40);
+    draw_line(&mut frame, [10.0, 10.0, 0.5], [200.0, 200.0, 0.5], &Charset::default(), None);
+    // The line goes down to the right at 45 degrees until it leaves the bottom of the screen.
+    let cells = drawn_cells(&frame);
+    assert_eq!(cells.len(), 30);
+    assert!(cells.iter().all(|&(x, y)| x == y));
+}
+
+#[test]
+fn nearer_line_hides_a_farther_one() {
+    let mut frame = Frame::new(20, 10);
+    let charset = Charset::default();
+    draw_line(&mut frame, [2.0, 5.0, 0.5], [17.0, 5.0, 0.5], &charset, None);
+    // A line behind the first does not show where they cross, and one in front of it does.
+    draw_line(&mut frame, [6.0, 1.0, 0.8], [6.0, 9.0, 0.8], &charset, None);
+    draw_line(&mut frame, [12.0, 1.0, 0.2], [12.0, 9.0, 0.2], &charset, None);
+    assert_eq!(frame.row(5)[6], '-');
+    assert_eq!(frame.row(5)[12], '|');
+    assert_eq!(frame.row(4)[6], '|');
+}
+
+#[test]
+fn diagonal_lines_use_slashes() {
+    let charset = Charset::default();
+    // Cells are twice as tall as they are wide, so a line at 45 degrees goes two cells across for every cell down.
+    assert_eq!(line_glyph(2.0, 1.0, &charset), '\\');
+    assert_eq!(line_glyph(-2.0, -1.0, &charset), '\\');
+    assert_eq!(line_glyph(2.0, -1.0, &charset), '/');
+    assert_eq!(line_glyph(-2.0, 1.0, &charset), '/');
+    // Going one cell across for every cell down is steeper, but still closer to diagonal than vertical.
+    assert_eq!(line_glyph(1.0, 1.0, &charset), '\\');
+    assert_eq!(line_glyph(10.0, 1.0, &charset), '-');
+    assert_eq!(line_glyph(1.0, 10.0, &charset), '|');
+    // Every cell of a diagonal line drawn into a frame gets the same slash.
+    let mut frame = Frame::new(40, 20);
+    draw_line(&mut frame, [2.0, 17.0, 0.0], [32.0, 2.0, 0.0], &charset, None);
+    let cells = drawn_cells(&frame);
+    assert!(cells.len() > 20);
+    assert!(cells.iter().all(|&(x, y)| frame.row(y)[x] == '/'));
+}
+
+#[test]
+fn depth_cued_line_fades_from_near_to_far() {
+    let mut frame = Frame::new(40, 1);
+    draw_depth_cued_line(&mut frame, [0.0, 0.0, 0.2], [39.0, 0.0, 0.8], [0.2, 0.8], None);
+    let row: Vec<usize> = frame.row(0).iter().map(|&c| DEPTH_CUE.iter().position(|&d| d as char == c).unwrap()).collect();
+    // The near end is the densest character and the far end the lightest, with every one in between along the way.
+    assert_eq!(row[0], DEPTH_CUE.len() - 1);
+    assert_eq!(row[39], 0);
+    assert!(row.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert!((0..DEPTH_CUE.len()).all(|shade| row.contains(&shade)));
+}
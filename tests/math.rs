@@ -0,0 +1,231 @@
+//! Tests of the vector and matrix math
+
+use simple_rust_cube::{add, approx_eq, cross, determinant, dot, inverse, length, matrix_approx_eq, matrix_times_matrix, matrix_times_vector, normalize, oscillation, rotation_axis, rotation_x, rotation_y, rotation_z, scale, scale_vec, sub, translation, transpose, Matrix, Vector, IDENTITY, VERTICES};
+
+#[test]
+fn equal_values_compare_equal() {
+    assert_eq!(Vector([1.0, 2.0, 3.0, 1.0]), Vector([1.0, 2.0, 3.0, 1.0]));
+    assert_ne!(Vector([1.0, 2.0, 3.0, 1.0]), Vector([1.0, 2.0, 3.0, 0.0]));
+    assert_eq!(translation(1.0, 2.0, 3.0), translation(1.0, 2.0, 3.0));
+    assert_ne!(translation(1.0, 2.0, 3.0), IDENTITY);
+}
+
+#[test]
+fn values_within_eps_compare_approximately_equal() {
+    let a = Vector([1.0, 2.0, 3.0, 1.0]);
+    assert!(approx_eq(&a, &Vector([1.0005, 1.9995, 3.0, 1.0]), 1e-3));
+    assert!(!approx_eq(&a, &Vector([1.002, 2.0, 3.0, 1.0]), 1e-3));
+    // A full turn is not exactly the identity, because of rounding.
+    let turned = rotation_y(std::f32::consts::TAU);
+    assert!(matrix_approx_eq(&turned, &IDENTITY, 1e-5));
+    assert!(!matrix_approx_eq(&rotation_y(0.01), &IDENTITY, 1e-5));
+}
+
+#[test]
+fn matrices_are_indexed_by_row_then_column() {
+    let mut m = translation(1.0, 2.0, 3.0);
+    // The translation is in the last column.
+    assert_eq!(m[(0, 3)], 1.0);
+    assert_eq!(m[(1, 3)], 2.0);
+    assert_eq!(m[(3, 0)], 0.0);
+    m[(2, 3)] = 5.0;
+    assert_eq!(m.0[3][2], 5.0);
+    assert_eq!(m, translation(1.0, 2.0, 5.0));
+}
+
+#[test]
+fn vectors_are_indexed_by_component() {
+    let mut v = Vector([1.0, 2.0, 3.0, 1.0]);
+    assert_eq!(v[1], 2.0);
+    v[2] = 4.0;
+    assert_eq!(v, Vector([1.0, 2.0, 4.0, 1.0]));
+}
+
+#[test]
+fn transposing_twice_gives_the_original() {
+    let m = translation(1.0, 2.0, 3.0) * rotation_x(0.3) * rotation_y(1.2);
+    assert_eq!(transpose(&transpose(&m)), m);
+    assert_eq!(transpose(&IDENTITY), IDENTITY);
+}
+
+#[test]
+fn transposing_swaps_rows_and_columns() {
+    let m = Matrix([
+        [1.0, 2.0, 3.0, 4.0],
+        [5.0, 6.0, 7.0, 8.0],
+        [9.0, 10.0, 11.0, 12.0],
+        [13.0, 14.0, 15.0, 16.0],
+    ]);
+    let expected = Matrix([
+        [1.0, 5.0, 9.0, 13.0],
+        [2.0, 6.0, 10.0, 14.0],
+        [3.0, 7.0, 11.0, 15.0],
+        [4.0, 8.0, 12.0, 16.0],
+    ]);
+    assert_eq!(transpose(&m), expected);
+    assert_eq!(transpose(&m)[(1, 2)], m[(2, 1)]);
+}
+
+#[test]
+fn inverse_undoes_a_rotation_and_translation() {
+    let m = translation(1.0, -2.0, 3.0) * rotation_y(0.7) * rotation_x(-0.4);
+    // Rotations and translations keep volumes the same.
+    assert!((determinant(&m) - 1.0).abs() < 1e-5);
+    let inv = inverse(&m).unwrap();
+    assert!(matrix_approx_eq(&(inv * m), &IDENTITY, 1e-5));
+    assert!(matrix_approx_eq(&(m * inv), &IDENTITY, 1e-5));
+    assert!(matrix_approx_eq(&inv, &(rotation_x(0.4) * rotation_y(-0.7) * translation(-1.0, 2.0, -3.0)), 1e-5));
+}
+
+#[test]
+fn determinant_of_a_scale_is_the_product_of_its_factors() {
+    assert!((determinant(&scale(2.0, 3.0, 0.5)) - 3.0).abs() < 1e-6);
+    assert!((determinant(&scale(-1.0, 1.0, 1.0)) + 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn singular_matrices_have_no_inverse() {
+    let flat = rotation_y(0.3) * scale(1.0, 0.0, 1.0);
+    assert_eq!(determinant(&flat).abs(), 0.0);
+    assert_eq!(inverse(&flat), None);
+}
+
+#[test]
+fn oscillation_swings_between_its_endpoints() {
+    use std::f32::consts::PI;
+    let (amplitude, frequency) = (0.8, 2.0);
+    assert_eq!(oscillation(0.0, amplitude, frequency), 0.0);
+    // A quarter of the way through the cycle it is at one end of its swing, and three quarters of the way at the other.
+    assert!((oscillation(PI / 4.0, amplitude, frequency) - amplitude).abs() < 1e-6);
+    assert!((oscillation(3.0 * PI / 4.0, amplitude, frequency) + amplitude).abs() < 1e-6);
+    // It never goes past either end.
+    assert!((0..100).map(|i| oscillation(i as f32 * 0.1, amplitude, frequency)).all(|angle| angle.abs() <= amplitude));
+}
+
+#[test]
+fn multiplying_by_the_identity_changes_nothing() {
+    let m = translation(1.0, 2.0, 3.0) * rotation_x(0.3);
+    assert_eq!(matrix_times_matrix(&IDENTITY, &m), m);
+    assert_eq!(matrix_times_matrix(&m, &IDENTITY), m);
+}
+
+#[test]
+fn product_of_two_rotations_applies_both() {
+    use std::f32::consts::FRAC_PI_2;
+    // Two turns around the same axis add up.
+    assert!(matrix_approx_eq(&matrix_times_matrix(&rotation_z(0.4), &rotation_z(0.7)), &rotation_z(1.1), 1e-6));
+    // A quarter turn around y takes +X to +Z, and then a quarter turn around x takes +Z to -Y.
+    let product = matrix_times_matrix(&rotation_x(FRAC_PI_2), &rotation_y(FRAC_PI_2));
+    let v = Vector([1.0, 0.0, 0.0, 1.0]);
+    assert!(approx_eq(&matrix_times_vector(&product, &v), &Vector([0.0, -1.0, 0.0, 1.0]), 1e-6));
+    let one_by_one = matrix_times_vector(&rotation_x(FRAC_PI_2), &matrix_times_vector(&rotation_y(FRAC_PI_2), &v));
+    assert!(approx_eq(&matrix_times_vector(&product, &v), &one_by_one, 1e-6));
+}
+
+#[test]
+fn identity_leaves_every_vertex_unchanged() {
+    for v in VERTICES {
+        assert_eq!(matrix_times_vector(&IDENTITY, &v), v);
+    }
+}
+
+#[test]
+fn full_turn_brings_a_vertex_back() {
+    use std::f32::consts::TAU;
+    let v = VERTICES[3];
+    for rotation in [rotation_x(TAU), rotation_y(TAU), rotation_z(TAU)] {
+        assert!(approx_eq(&(rotation * v), &v, 1e-5));
+    }
+}
+
+#[test]
+fn quarter_turn_around_x_takes_y_to_z() {
+    let turned = rotation_x(std::f32::consts::FRAC_PI_2) * Vector([0.0, 1.0, 0.0, 0.0]);
+    assert!(approx_eq(&turned, &Vector([0.0, 0.0, 1.0, 0.0]), 1e-6));
+}
+
+#[test]
+fn translation_moves_the_origin() {
+    let origin = Vector([0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(translation(1.5, -2.0, 3.0) * origin, Vector([1.5, -2.0, 3.0, 1.0]));
+    // Directions have no position, so they are not moved.
+    assert_eq!(translation(1.5, -2.0, 3.0) * Vector([0.0, 1.0, 0.0, 0.0]), Vector([0.0, 1.0, 0.0, 0.0]));
+}
+
+#[test]
+fn scale_stretches_each_axis_by_its_own_factor() {
+    assert_eq!(scale(2.0, 3.0, 0.5) * VERTICES[7], Vector([2.0, 3.0, 0.5, 1.0]));
+    assert_eq!(scale(2.0, 1.0, 1.0) * VERTICES[0], Vector([-2.0, -1.0, -1.0, 1.0]));
+}
+
+/// The unit vectors along the x, y and z axes
+const X : Vector = Vector([1.0, 0.0, 0.0, 0.0]);
+const Y : Vector = Vector([0.0, 1.0, 0.0, 0.0]);
+const Z : Vector = Vector([0.0, 0.0, 1.0, 0.0]);
+
+#[test]
+fn dot_products_of_the_axes() {
+    assert_eq!(dot(&X, &X), 1.0);
+    assert_eq!(dot(&X, &Y), 0.0);
+    assert_eq!(dot(&Y, &Z), 0.0);
+    // The w components are left out.
+    assert_eq!(dot(&Vector([1.0, 2.0, 3.0, 1.0]), &Vector([4.0, -5.0, 6.0, 1.0])), 12.0);
+}
+
+#[test]
+fn cross_products_of_the_axes() {
+    assert_eq!(cross(&X, &Y), Z);
+    assert_eq!(cross(&Y, &Z), X);
+    assert_eq!(cross(&Z, &X), Y);
+    assert_eq!(cross(&Y, &X), Vector([0.0, 0.0, -1.0, 0.0]));
+    assert_eq!(cross(&X, &X), Vector([0.0; 4]));
+}
+
+#[test]
+fn length_of_a_three_four_five_triangle() {
+    assert_eq!(length(&Vector([3.0, 4.0, 0.0, 1.0])), 5.0);
+    let unit = normalize(&Vector([3.0, 4.0, 0.0, 1.0]));
+    assert!(approx_eq(&unit, &Vector([0.6, 0.8, 0.0, 1.0]), 1e-6));
+}
+
+#[test]
+fn zero_vector_stays_zero_when_normalized() {
+    let zero = normalize(&Vector([0.0, 0.0, 0.0, 0.0]));
+    assert_eq!(zero, Vector([0.0; 4]));
+    assert!(zero.0.iter().all(|c| !c.is_nan()));
+}
+
+#[test]
+fn adding_a_direction_to_a_point_gives_a_point() {
+    assert_eq!(add(&Vector([1.0, 2.0, 3.0, 1.0]), &Vector([0.5, -2.0, 1.0, 0.0])), Vector([1.5, 0.0, 4.0, 1.0]));
+}
+
+#[test]
+fn subtracting_two_points_gives_a_direction() {
+    assert_eq!(sub(&Vector([1.0, 2.0, 3.0, 1.0]), &Vector([0.5, -2.0, 1.0, 1.0])), Vector([0.5, 4.0, 2.0, 0.0]));
+}
+
+#[test]
+fn scaling_a_vector_leaves_w_alone() {
+    assert_eq!(scale_vec(&Vector([1.0, -2.0, 3.0, 1.0]), 2.0), Vector([2.0, -4.0, 6.0, 1.0]));
+}
+
+#[test]
+fn multiplying_with_the_operator_is_the_same_as_the_function() {
+    let m = translation(1.0, -2.0, 3.0) * rotation_y(0.7);
+    let v = Vector([0.3, 1.5, -2.0, 1.0]);
+    assert_eq!(m * v, matrix_times_vector(&m, &v));
+    assert_eq!(m * rotation_x(0.2), matrix_times_matrix(&m, &rotation_x(0.2)));
+}
+
+#[test]
+fn rotation_around_the_y_axis_is_rotation_y() {
+    for t in [0.0, 0.3, -1.2, std::f32::consts::PI, 5.0] {
+        assert!(matrix_approx_eq(&rotation_axis(&Y, t), &rotation_y(t), 1e-6), "at {}", t);
+        // The axis does not need to be of unit length.
+        assert!(matrix_approx_eq(&rotation_axis(&scale_vec(&Y, 3.0), t), &rotation_y(t), 1e-6), "at {}", t);
+    }
+    // Around the other axes it turns the other way from rotation_x and rotation_z.
+    assert!(matrix_approx_eq(&rotation_axis(&X, 0.7), &rotation_x(-0.7), 1e-6));
+    assert!(matrix_approx_eq(&rotation_axis(&Z, 0.7), &rotation_z(-0.7), 1e-6));
+}
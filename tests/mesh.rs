@@ -0,0 +1,99 @@
+//! Tests of rendering meshes other than the built-in cube
+
+use simple_rust_cube::{add, length, render_frame, translation, unique_edges, Mesh, RenderMode, RenderOptions, Vector, FIT_RADIUS};
+
+#[test]
+fn custom_shape_goes_through_the_renderer() {
+    // A single square facing the camera, wound clockwise as seen from the front like the faces of the cube
+    let square = Mesh {
+        vertices: vec![
+            Vector([-1.0, -1.0, 0.0, 1.0]),
+            Vector([-1.0, 1.0, 0.0, 1.0]),
+            Vector([1.0, 1.0, 0.0, 1.0]),
+            Vector([1.0, -1.0, 0.0, 1.0]),
+        ],
+        faces: vec![vec![0, 1, 2, 3]],
+    };
+    let model_to_world = translation(0.0, 0.0, -3.0);
+    let wireframe = render_frame(&square, &model_to_world, &RenderOptions::default(), 40, 20);
+    let rows: Vec<String> = (0..wireframe.height()).map(|y| wireframe.row(y).iter().collect()).collect();
+    // The top and bottom edges are horizontal lines and the sides are vertical ones, which meet them at the corners.
+    let drawn: Vec<&String> = rows.iter().filter(|row| row.trim() != "").collect();
+    let horizontal = |row: &str| row.trim().chars().all(|c| c == '-' || c == '|') && row.contains("---");
+    assert!(horizontal(drawn.first().unwrap()));
+    assert!(horizontal(drawn.last().unwrap()));
+    assert!(drawn[1..drawn.len() - 1].iter().all(|row| row.trim().starts_with('|') && row.trim().ends_with('|')));
+
+    let options = RenderOptions { mode: RenderMode::Points, ..RenderOptions::default() };
+    let points = render_frame(&square, &model_to_world, &options, 40, 20);
+    let count = (0..points.height()).map(|y| points.row(y).iter().filter(|&&c| c == '*').count()).sum::<usize>();
+    assert_eq!(count, 4);
+}
+
+/// A small triangle far off to the upper right of the origin
+fn off_center_triangle() -> Mesh {
+    Mesh {
+        vertices: vec![Vector([10.0, 20.0, 5.0, 1.0]), Vector([10.0, 20.5, 5.0, 1.0]), Vector([10.5, 20.0, 5.0, 1.0])],
+        faces: vec![vec![0, 1, 2]],
+    }
+}
+
+#[test]
+fn bounds_hold_every_vertex() {
+    let (low, high) = off_center_triangle().bounds().unwrap();
+    assert_eq!(low, Vector([10.0, 20.0, 5.0, 1.0]));
+    assert_eq!(high, Vector([10.5, 20.5, 5.0, 1.0]));
+    assert_eq!(Mesh { vertices: Vec::new(), faces: Vec::new() }.bounds(), None);
+}
+
+#[test]
+fn fitted_shape_is_drawn_in_the_middle_of_the_screen() {
+    let mut mesh = off_center_triangle();
+    let options = RenderOptions { mode: RenderMode::Points, ..RenderOptions::default() };
+    let model_to_world = translation(0.0, 0.0, -4.0);
+    // Before fitting, the triangle is far outside the view.
+    let before = render_frame(&mesh, &model_to_world, &options, 40, 20);
+    assert!((0..before.height()).all(|y| before.row(y).iter().all(|&c| c == ' ')));
+
+    let fit = mesh.fit_transform();
+    for vertex in &mut mesh.vertices {
+        *vertex = fit * *vertex;
+    }
+    let (low, high) = mesh.bounds().unwrap();
+    assert!(length(&add(&low, &high)) < 1e-5);
+    let radius = mesh.vertices.iter().map(|v| length(&Vector([v.0[0], v.0[1], v.0[2], 0.0]))).fold(0.0, f32::max);
+    assert!((radius - FIT_RADIUS).abs() < 1e-5);
+
+    // The middle of the bounds of the points on the screen is the center of the screen.
+    let after = render_frame(&mesh, &model_to_world, &options, 40, 20);
+    let points: Vec<(usize, usize)> = (0..20).flat_map(|y| (0..40).map(move |x| (x, y))).filter(|&(x, y)| after.row(y)[x] == '*').collect();
+    assert_eq!(points.len(), 3);
+    let (min_x, max_x) = (points.iter().map(|p| p.0).min().unwrap(), points.iter().map(|p| p.0).max().unwrap());
+    let (min_y, max_y) = (points.iter().map(|p| p.1).min().unwrap(), points.iter().map(|p| p.1).max().unwrap());
+    assert!((min_x + max_x).abs_diff(40) <= 1);
+    assert!((min_y + max_y).abs_diff(20) <= 1);
+}
+
+#[test]
+fn cube_has_twelve_edges_each_between_two_faces() {
+    let edges = unique_edges(&Mesh::cube().faces);
+    assert_eq!(edges.len(), 12);
+    for (i, edge) in edges.iter().enumerate() {
+        assert!(edge.vertices[0] < edge.vertices[1]);
+        assert_eq!(edge.faces.len(), 2, "{:?}", edge);
+        assert!(edges[..i].iter().all(|other| other.vertices != edge.vertices));
+    }
+}
+
+#[test]
+fn platonic_solids_have_the_right_numbers_of_vertices_faces_and_edges() {
+    for (name, mesh, vertices, faces, edges) in [("tetrahedron", Mesh::tetrahedron(), 4, 4, 6), ("octahedron", Mesh::octahedron(), 6, 8, 12), ("icosahedron", Mesh::icosahedron(), 12, 20, 30)] {
+        assert_eq!(mesh.vertices.len(), vertices, "{}", name);
+        assert_eq!(mesh.faces.len(), faces, "{}", name);
+        assert!(mesh.faces.iter().all(|face| face.len() == 3 && face.iter().all(|&i| i < vertices)), "{}", name);
+        // Every edge borders exactly two faces, so the solid is closed.
+        let unique = unique_edges(&mesh.faces);
+        assert_eq!(unique.len(), edges, "{}", name);
+        assert!(unique.iter().all(|edge| edge.faces.len() == 2), "{}", name);
+    }
+}
@@ -0,0 +1,126 @@
+//! Tests that each render mode draws the kind of thing it should
+
+use simple_rust_cube::{normal_color, phong, phong_shade, render_frame, rotation_x, rotation_y, shade, translation, Frame, Mesh, Matrix, RenderMode, RenderOptions, Vector, AMBIENT, SHADES};
+
+/// Transformation that puts the cube in front of the camera, turned so that three of its faces can be seen
+fn placement() -> Matrix {
+    translation(0.0, 0.0, -4.0) * rotation_x(0.5) * rotation_y(0.6)
+}
+
+/// Renders the cube in the given mode
+fn render(mode: RenderMode) -> Frame {
+    let options = RenderOptions { mode, ..RenderOptions::default() };
+    render_frame(&Mesh::cube(), &placement(), &options, 60, 30)
+}
+
+/// All the characters that were drawn in a frame, leaving out the spaces
+fn drawn(frame: &Frame) -> Vec<char> {
+    (0..frame.height()).flat_map(|y| frame.row(y).to_vec()).filter(|&c| c != ' ').collect()
+}
+
+#[test]
+fn wireframe_draws_only_lines() {
+    let chars = drawn(&render(RenderMode::Wireframe));
+    assert!(!chars.is_empty());
+    assert!(chars.iter().all(|c| "-|/\\".contains(*c)));
+}
+
+#[test]
+fn filled_draws_only_the_fill_character() {
+    let chars = drawn(&render(RenderMode::Filled));
+    assert!(chars.len() > 100);
+    assert!(chars.iter().all(|&c| c == '#'));
+}
+
+#[test]
+fn shaded_faces_get_different_shades() {
+    let mut chars = drawn(&render(RenderMode::Shaded));
+    assert!(chars.iter().all(|&c| SHADES.contains(&(c as u8))));
+    chars.sort();
+    chars.dedup();
+    assert!(chars.len() >= 2, "only {:?}", chars);
+}
+
+#[test]
+fn points_draws_only_at_the_visible_vertices() {
+    let frame = render(RenderMode::Points);
+    let chars = drawn(&frame);
+    assert!(chars.iter().all(|&c| c == '*'));
+    // Three faces of the cube can be seen, which between them have all but the vertex at the back.
+    assert_eq!(chars.len(), 7);
+    let wireframe = render(RenderMode::Wireframe);
+    for y in 0..frame.height() {
+        for x in 0..frame.width() {
+            if frame.row(y)[x] == '*' {
+                // Each point is at a corner of the wireframe, so an edge passes through its cell.
+                assert_ne!(wireframe.row(y)[x], ' ');
+            }
+        }
+    }
+}
+
+#[test]
+fn phong_faces_get_shades() {
+    let chars = drawn(&render(RenderMode::Phong));
+    assert!(chars.len() > 100);
+    assert!(chars.iter().all(|&c| SHADES.contains(&(c as u8))));
+}
+
+#[test]
+fn face_reflecting_the_light_at_the_viewer_gets_the_brightest_shade() {
+    let normal = Vector([0.0, 0.0, 1.0, 0.0]);
+    let light = Vector([1.0, 0.0, 1.0, 0.0]);
+    // The light is reflected off the face towards the upper left, where the viewer is.
+    let reflection = Vector([-1.0, 0.0, 1.0, 0.0]);
+    assert_eq!(phong_shade(&normal, &light, &reflection, 16.0), *SHADES.last().unwrap() as char);
+    // Without the highlight, the face is only lit by the diffuse light.
+    assert!(phong(&normal, &light, &Vector([1.0, 0.0, 1.0, 0.0]), 16.0) < phong(&normal, &light, &reflection, 16.0));
+    assert_ne!(shade(&normal, &light), *SHADES.last().unwrap() as char);
+}
+
+#[test]
+fn higher_shininess_narrows_the_highlight() {
+    let normal = Vector([0.0, 0.0, 1.0, 0.0]);
+    let light = Vector([1.0, 0.0, 1.0, 0.0]);
+    // Seen from a little away from the direction of the reflection, a shinier face has less of a highlight.
+    let view = Vector([-0.8, 0.0, 1.0, 0.0]);
+    assert!(phong(&normal, &light, &view, 64.0) < phong(&normal, &light, &view, 4.0));
+}
+
+#[test]
+fn face_turned_away_from_the_light_only_gets_ambient_light() {
+    let normal = Vector([0.0, 0.0, 1.0, 0.0]);
+    assert_eq!(phong(&normal, &Vector([0.0, 0.0, -1.0, 0.0]), &normal, 16.0), AMBIENT);
+}
+
+#[test]
+fn normal_colors_follow_the_axes() {
+    assert_eq!(normal_color(&Vector([1.0, 0.0, 0.0, 0.0])), [255, 128, 128]);
+    assert_eq!(normal_color(&Vector([0.0, -3.0, 0.0, 0.0])), [128, 0, 128]);
+    assert_eq!(normal_color(&Vector([0.0, 0.0, 1.0, 0.0])), [128, 128, 255]);
+}
+
+#[test]
+fn face_facing_x_is_drawn_in_its_normal_color() {
+    // Turned a quarter turn around the y-axis, the +x face of the cube faces the camera and fills the middle of the screen.
+    let options = RenderOptions { mode: RenderMode::Normals, ..RenderOptions::default() };
+    let placement = translation(0.0, 0.0, -4.0) * rotation_y(std::f32::consts::FRAC_PI_2);
+    let frame = render_frame(&Mesh::cube(), &placement, &options, 60, 30);
+    assert_eq!(frame.row(15)[30], '#');
+    assert_eq!(frame.row_rgb(15)[30], Some([255, 128, 128]));
+    assert_eq!(frame.row_colors(15)[30], None);
+    let row = frame.row_to_string(15);
+    assert!(row.contains("\x1b[38;2;255;128;128m#"), "{:?}", row);
+    // The color is reset after the last cell of the face.
+    assert!(row.contains("#\x1b[0m "), "{:?}", row);
+}
+
+#[test]
+fn face_towards_the_light_is_brightest_and_away_from_it_darkest() {
+    let light = Vector([0.0, 1.0, 0.0, 0.0]);
+    assert_eq!(shade(&Vector([0.0, 1.0, 0.0, 0.0]), &light), *SHADES.last().unwrap() as char);
+    assert_eq!(shade(&Vector([0.0, -1.0, 0.0, 0.0]), &light), SHADES[0] as char);
+    // A face at an angle to the light gets a shade in between.
+    let between = shade(&Vector([1.0, 1.0, 0.0, 0.0]), &light);
+    assert!(between != SHADES[0] as char && between != *SHADES.last().unwrap() as char);
+}
@@ -0,0 +1,54 @@
+//! Tests of loading meshes from the text of OBJ files
+
+use simple_rust_cube::obj::{parse_obj, ObjError};
+use simple_rust_cube::{face_normal, Vector};
+
+/// A tetrahedron with its faces wound counterclockwise as seen from outside, as OBJ files have them, along with a comment, a normal and texture and normal indices to skip
+const TETRAHEDRON : &str = "\
+# a tetrahedron
+v 1 1 1
+v 1 -1 -1
+v -1 1 -1
+v -1 -1 1
+vn 0 0 1
+f 1 2 3
+f 1/1 3/1 4/1
+f 1//1 4//1 2//1
+f 2 4 3
+";
+
+#[test]
+fn tetrahedron_is_loaded_with_its_faces_wound_clockwise() {
+    let mesh = parse_obj(TETRAHEDRON).unwrap();
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.vertices[1], Vector([1.0, -1.0, -1.0, 1.0]));
+    // Indices count from 1 in the file and from 0 in the mesh, and the faces are turned the other way.
+    assert_eq!(mesh.faces, [vec![2, 1, 0], vec![3, 2, 0], vec![1, 3, 0], vec![2, 3, 1]]);
+    // With the faces wound like those of the cube, every normal points out of the tetrahedron, away from its center at the origin.
+    for face in &mesh.faces {
+        let normal = face_normal(face, &mesh.vertices);
+        let corner = mesh.vertices[face[0]];
+        assert!((0..3).map(|axis| normal.0[axis] * corner.0[axis]).sum::<f32>() > 0.0);
+    }
+}
+
+/// The error parsing the text gives
+fn error_of(text: &str) -> ObjError {
+    parse_obj(text).unwrap_err()
+}
+
+#[test]
+fn vertex_with_a_bad_coordinate_is_an_error() {
+    assert_eq!(error_of("v 1 2 3\nv 1 2\n"), ObjError { line: 2, message: "a vertex needs x, y and z coordinates".to_string() });
+    assert_eq!(error_of("v 1 two 3\n").to_string(), "line 1: invalid coordinate 'two'");
+}
+
+#[test]
+fn face_with_a_bad_index_is_an_error() {
+    let vertices = "v 0 0 0\nv 1 0 0\nv 0 1 0\n";
+    assert_eq!(error_of(&format!("{}f 1 2 x\n", vertices)).to_string(), "line 4: invalid vertex index 'x'");
+    assert_eq!(error_of(&format!("{}f 1 2 -1\n", vertices)).to_string(), "line 4: negative vertex index -1 is not supported");
+    assert_eq!(error_of(&format!("{}f 0 1 2\n", vertices)).to_string(), "line 4: vertex index 0 is out of range");
+    assert_eq!(error_of(&format!("{}f 1 2 4\n", vertices)).to_string(), "line 4: vertex index 4 is out of range");
+    assert_eq!(error_of(&format!("{}f 1 2\n", vertices)).to_string(), "line 4: a face needs at least three vertices");
+}
@@ -0,0 +1,55 @@
+//! Tests of writing frames with --plain, without any escape sequences
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Runs the binary on a small screen with --plain and the given arguments, writing input to its stdin, and returns what it wrote to stdout
+fn run_plain(args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube"))
+        .args(["--width", "30", "--height", "10", "--plain"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Checks that the output holds count frames of 10 lines of 30 characters, with a blank line between each frame and the next
+fn assert_frames(output: &str, count: usize) {
+    let frames: Vec<&str> = output.strip_suffix('\n').unwrap().split("\n\n").collect();
+    assert_eq!(frames.len(), count);
+    for frame in frames {
+        let lines: Vec<&str> = frame.split('\n').collect();
+        assert_eq!(lines.len(), 10);
+        assert!(lines.iter().all(|line| line.chars().count() == 30));
+    }
+}
+
+#[test]
+fn plain_animation_has_no_escape_sequences() {
+    let output = run_plain(&["--frames", "3", "--axes", "--mode", "normals", "--hud"], "");
+    assert!(!output.contains('\x1b'), "{:?}", output);
+    assert!(output.contains('#'));
+    assert_frames(&output, 3);
+}
+
+#[test]
+fn plain_script_has_no_escape_sequences() {
+    let output = run_plain(&["--script", "--axes", "--glyphs", "braille"], "render\nyaw 0.5\nrender\n");
+    assert!(!output.contains('\x1b'), "{:?}", output);
+    // Braille shows four rows of cells in each row of characters, so the 10 rows of the screen are still 10 lines.
+    assert_frames(&output, 2);
+}
+
+#[test]
+fn animation_into_a_pipe_is_plain_without_asking() {
+    let output = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube")).args(["--width", "30", "--height", "10", "--frames", "2", "--axes"]).output().unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert!(!output.contains('\x1b'), "{:?}", output);
+    assert_frames(&output, 2);
+}
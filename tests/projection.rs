@@ -0,0 +1,93 @@
+//! Tests of projecting points from camera coordinates onto the screen
+
+use simple_rust_cube::{approx_eq, length, look_at, orthographic, perspective, project, projection_matrix, render_frame, translation, viewport, CullMode, Matrix, Mesh, RenderMode, RenderOptions, Vector, IDENTITY, VERTICES};
+
+#[test]
+fn point_on_the_near_plane_has_depth_minus_one() {
+    use std::f32::consts::FRAC_PI_2;
+    // With a field of view of 90 degrees, the edges of the view are as far to the side as they are ahead.
+    let projection = perspective(FRAC_PI_2, 1.0, 1.0, 10.0);
+    let ndc = project(&projection, &Vector([0.5, -0.25, -1.0, 1.0])).unwrap();
+    assert!(approx_eq(&ndc, &Vector([0.5, -0.25, -1.0, 1.0]), 1e-6), "{:?}", ndc);
+    let far = project(&projection, &Vector([0.0, 0.0, -10.0, 1.0])).unwrap();
+    assert!((far.0[2] - 1.0).abs() < 1e-5);
+}
+
+#[test]
+fn narrower_field_of_view_makes_things_bigger() {
+    let point = Vector([1.0, 1.0, -4.0, 1.0]);
+    let size = |fov_y: f32| project(&perspective(fov_y.to_radians(), 1.0, 0.1, 100.0), &point).unwrap().0[1];
+    assert!(size(45.0) > size(90.0));
+    // Halving the tangent of half the field of view doubles the size.
+    let half = 2.0 * (0.5f32).atan().to_degrees();
+    assert!((size(half) - 2.0 * size(90.0)).abs() < 1e-5);
+}
+
+/// Lengths on the screen of the bottom edge of the front face and of the back face of the cube, seen face on through a projection
+fn front_and_back_edges(projection: &Matrix) -> [f32; 2] {
+    let on_screen = |i: usize| project(projection, &(translation(0.0, 0.0, -4.0) * VERTICES[i])).unwrap();
+    // Vertices 1 and 3 are at the bottom of the front face, and 0 and 2 at the bottom of the back face.
+    [(1, 3), (0, 2)].map(|(a, b)| on_screen(b).0[0] - on_screen(a).0[0])
+}
+
+#[test]
+fn orthographic_edges_keep_their_length_at_any_distance() {
+    let [front, back] = front_and_back_edges(&orthographic(5.0, 1.0, 0.1, 100.0));
+    assert!((front - back).abs() < 1e-6, "{} and {}", front, back);
+    let [front, back] = front_and_back_edges(&perspective(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0));
+    assert!(front > back * 1.5, "{} and {}", front, back);
+}
+
+#[test]
+fn face_on_cube_looks_square_on_cells_of_the_given_aspect() {
+    for cell_aspect in [0.5, 0.4, 1.0] {
+        for (width, height) in [(80, 24), (60, 60)] {
+            let options = RenderOptions { cell_aspect, ..RenderOptions::default() };
+            let projection = projection_matrix(&options, width, height);
+            let on_screen = |i: usize| viewport(&project(&projection, &(translation(0.0, 0.0, -4.0) * VERTICES[i])).unwrap(), width, height);
+            // Vertices 1, 3 and 5 are the bottom left, bottom right and top left corners of the front face.
+            let across = on_screen(3)[0] - on_screen(1)[0];
+            let down = on_screen(1)[1] - on_screen(5)[1];
+            // A cell is cell_aspect times as wide as it is tall, so a square covers 1 / cell_aspect times as many columns as rows.
+            assert!((across * cell_aspect / down - 1.0).abs() < 1e-4, "{} on {}x{}: {} by {}", cell_aspect, width, height, across, down);
+        }
+    }
+}
+
+#[test]
+fn camera_looking_at_the_cube_puts_its_center_in_the_middle_of_the_screen() {
+    let (width, height) = (80, 24);
+    let (target, up) = (Vector([0.0, 0.0, 0.0, 1.0]), Vector([0.0, 1.0, 0.0, 0.0]));
+    for eye in [Vector([0.0, 0.0, 5.0, 1.0]), Vector([3.0, 2.0, 4.0, 1.0])] {
+        let view = look_at(&eye, &target, &up);
+        // The camera ends up at the origin of its coordinates, with the cube straight ahead down the -z axis.
+        let center = view * target;
+        assert!(approx_eq(&center, &Vector([0.0, 0.0, -length(&eye), 1.0]), 1e-5), "{:?}", center);
+        let projection = projection_matrix(&RenderOptions::default(), width, height);
+        let [x, y, _] = viewport(&project(&projection, &center).unwrap(), width, height);
+        assert!((x - width as f32 / 2.0).abs() < 1e-4 && (y - height as f32 / 2.0).abs() < 1e-4, "{} {}", x, y);
+    }
+}
+
+#[test]
+fn vertex_in_the_plane_of_the_camera_is_not_projected() {
+    let projection = perspective(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+    assert_eq!(project(&projection, &Vector([0.0, 0.0, 0.0, 1.0])), None);
+    assert_eq!(project(&projection, &Vector([1.0, 1.0, 0.0, 1.0])), None);
+    assert_eq!(project(&projection, &Vector([1.0, 1.0, 2.0, 1.0])), None);
+}
+
+#[test]
+fn triangle_reaching_the_camera_is_drawn_only_where_it_is_in_view() {
+    // The bottom edge is ahead of the camera, and the top corner is level with it, above it.
+    let triangle = Mesh { vertices: vec![Vector([-1.0, 0.0, -4.0, 1.0]), Vector([0.0, 1.0, 0.0, 1.0]), Vector([1.0, 0.0, -4.0, 1.0])], faces: vec![vec![0, 1, 2]] };
+    let (width, height) = (40, 20);
+    for mode in [RenderMode::Wireframe, RenderMode::Filled, RenderMode::Shaded, RenderMode::Points] {
+        let options = RenderOptions { mode, cull_mode: CullMode::None, ..RenderOptions::default() };
+        let frame = render_frame(&triangle, &IDENTITY, &options, width, height);
+        // Everything but the corner at the camera is above the middle of the screen, so nothing may be drawn below it.
+        for y in height / 2 + 1..height {
+            assert!(frame.row(y).iter().all(|&c| c == ' '), "{:?}: row {} is {:?}", mode, y, frame.row_to_string(y));
+        }
+    }
+}
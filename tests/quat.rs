@@ -0,0 +1,84 @@
+//! Tests of the quaternion rotations
+
+use simple_rust_cube::quat::{quat_dot, slerp, tumble, Quat};
+use simple_rust_cube::{matrix_approx_eq, rotation_axis, rotation_x, rotation_y, rotation_z, Vector};
+
+#[test]
+fn tumble_composes_the_rotations_around_each_axis() {
+    let t = 1.5;
+    let (rx, ry, rz) = (0.4, 0.33, -0.2);
+    let expected = rotation_y(t * ry) * rotation_x(t * rx) * rotation_z(t * rz);
+    assert!(matrix_approx_eq(&tumble(t, [rx, ry, rz]).to_matrix(), &expected, 1e-5));
+}
+
+#[test]
+fn tumbling_around_two_axes_differs_from_spinning_around_one() {
+    let t = 2.0;
+    let y_only = tumble(t, [0.0, 0.33, 0.0]).to_matrix();
+    assert!(matrix_approx_eq(&y_only, &rotation_y(t * 0.33), 1e-5));
+    let tumbling = tumble(t, [0.5, 0.33, 0.0]).to_matrix();
+    assert!(!matrix_approx_eq(&tumbling, &y_only, 1e-3));
+}
+
+#[test]
+fn quaternion_turns_the_same_way_as_rotation_axis() {
+    for (axis, angle) in [([0.0, 1.0, 0.0], 0.8), ([1.0, 0.0, 0.0], -1.1), ([1.0, 2.0, -0.5], 2.4), ([0.3, -0.3, 1.0], 4.0)] {
+        let axis = Vector([axis[0], axis[1], axis[2], 0.0]);
+        let q = Quat::from_axis_angle(&axis, angle);
+        assert!(matrix_approx_eq(&q.to_matrix(), &rotation_axis(&axis, angle), 1e-5), "{:?} by {}", axis, angle);
+    }
+}
+
+/// Whether two quaternions stand for the same rotation, which q and -q both do
+fn same_rotation(a: &Quat, b: &Quat) -> bool {
+    (quat_dot(a, b).abs() - 1.0).abs() < 1e-5
+}
+
+/// A quaternion turning by angle around the y-axis
+fn yaw(angle: f32) -> Quat {
+    Quat::from_axis_angle(&Vector([0.0, 1.0, 0.0, 0.0]), angle)
+}
+
+#[test]
+fn slerp_starts_at_a_and_ends_at_b() {
+    let (a, b) = (yaw(0.3), Quat::from_axis_angle(&Vector([1.0, 1.0, 0.0, 0.0]), 1.7));
+    assert!(same_rotation(&slerp(&a, &b, 0.0), &a));
+    assert!(same_rotation(&slerp(&a, &b, 1.0), &b));
+}
+
+#[test]
+fn slerp_halfway_is_as_far_from_either_end() {
+    let (a, b) = (yaw(0.3), Quat::from_axis_angle(&Vector([1.0, 1.0, 0.0, 0.0]), 1.7));
+    let halfway = slerp(&a, &b, 0.5);
+    assert!((quat_dot(&halfway, &a) - quat_dot(&halfway, &b)).abs() < 1e-5);
+    // It goes at a constant speed, so a quarter of the way is half as far along as halfway.
+    let angle = |q: &Quat| quat_dot(q, &a).clamp(-1.0, 1.0).acos();
+    assert!((angle(&slerp(&a, &b, 0.25)) * 2.0 - angle(&halfway)).abs() < 1e-4);
+    // Between two turns around one axis, halfway is the turn by the angle halfway between them.
+    assert!(same_rotation(&slerp(&yaw(0.2), &yaw(1.0), 0.5), &yaw(0.6)));
+}
+
+#[test]
+fn slerp_takes_the_shorter_way_round() {
+    let (a, b) = (yaw(0.2), yaw(1.0));
+    // -b is the same rotation as b, but its dot product with a is negative.
+    let minus_b = Quat(b.0.map(|c| -c));
+    assert!(quat_dot(&a, &minus_b) < 0.0);
+    for t in [0.25, 0.5, 0.75] {
+        assert!(same_rotation(&slerp(&a, &minus_b, t), &slerp(&a, &b, t)), "at {}", t);
+    }
+    assert!(same_rotation(&slerp(&a, &minus_b, 0.5), &yaw(0.6)));
+}
+
+#[test]
+fn slerp_between_nearly_the_same_rotations_stays_between_them() {
+    let (a, b) = (yaw(0.5), yaw(0.5001));
+    for t in [0.0, 0.5, 1.0] {
+        let q = slerp(&a, &b, t);
+        assert!(q.0.iter().all(|c| c.is_finite()), "at {}: {:?}", t, q);
+        assert!((quat_dot(&q, &q) - 1.0).abs() < 1e-5);
+        assert!(same_rotation(&q, &yaw(0.5 + 0.0001 * t)));
+    }
+    // The very same rotation is left as it is.
+    assert!(same_rotation(&slerp(&a, &a, 0.5), &a));
+}
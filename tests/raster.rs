@@ -0,0 +1,86 @@
+//! Tests of how points on the screen are put into cells
+
+use simple_rust_cube::{draw_antialiased_line, draw_line, fill_triangle, render_frame, screen_cell, Charset, Coverage, Frame, Mesh, Projection, RenderOptions, Vector, IDENTITY};
+
+#[test]
+fn points_go_in_the_nearest_cell() {
+    assert_eq!(screen_cell(12.6, 3.2), Some([13, 3]));
+    assert_eq!(screen_cell(12.4, 3.5), Some([12, 4]));
+    // Just left of the middle of the first cell is still in it, but further left is off the screen.
+    assert_eq!(screen_cell(-0.4, 0.0), Some([0, 0]));
+    assert_eq!(screen_cell(-0.6, 0.0), None);
+    assert_eq!(screen_cell(0.0, -0.6), None);
+}
+
+/// Renders a single vertex as a marker, with a projection that puts x = 0 at the left of the screen and x = 5 at the right
+fn render_vertex(x: f32) -> Vec<String> {
+    let mesh = Mesh { vertices: vec![Vector([x - 2.5, 0.0, -3.0, 1.0])], faces: Vec::new() };
+    let options = RenderOptions { projection: Projection::Orthographic, vertex_markers: true, ..RenderOptions::default() };
+    let frame = render_frame(&mesh, &IDENTITY, &options, 40, 20);
+    (0..frame.height()).map(|y| frame.row(y).iter().collect()).collect()
+}
+
+#[test]
+fn vertex_is_rounded_to_the_nearest_cell() {
+    // The screen is 40 cells wide over 5 units, so 1.575 units from the left is at x = 12.6.
+    let rows = render_vertex(1.575);
+    assert_eq!(rows[10].find('*'), Some(13));
+    assert_eq!(rows.iter().filter(|row| row.contains('*')).count(), 1);
+}
+
+#[test]
+fn vertex_left_of_the_screen_is_not_drawn() {
+    let rows = render_vertex(-0.1);
+    assert!(rows.iter().all(|row| !row.contains('*')));
+}
+
+#[test]
+fn lines_include_both_ends() {
+    let mut frame = Frame::new(20, 10);
+    let charset = Charset::default();
+    // Two edges meeting at (12, 7), one from above and one from the left
+    draw_line(&mut frame, [12.0, 1.0, 0.5], [12.0, 7.0, 0.5], &charset, None);
+    draw_line(&mut frame, [2.0, 7.0, 0.5], [12.0, 7.0, 0.5], &charset, None);
+    assert_ne!(frame.row(7)[12], ' ');
+    assert_eq!(frame.row(1)[12], '|');
+    assert_eq!(frame.row(7)[2], '-');
+    // Nothing is drawn past the ends.
+    assert_eq!(frame.row(8)[12], ' ');
+    assert_eq!(frame.row(7)[13], ' ');
+    assert_eq!(frame.row(0)[12], ' ');
+    assert_eq!(frame.row(7)[1], ' ');
+}
+
+#[test]
+fn shallow_antialiased_line_fades_between_rows() {
+    let mut coverage = Coverage::new(30, 10);
+    // A line rising one row every five columns
+    draw_antialiased_line(&mut coverage, [0.0, 2.0, 0.5], [20.0, 6.0, 0.5], None);
+    let amounts: Vec<f32> = (0..10).flat_map(|y| (0..30).map(move |x| (x, y))).map(|(x, y)| coverage.get(x, y)).filter(|&a| a > 0.0).collect();
+    // Each column is covered by one full amount in all, split between cells, and many cells are only partly covered.
+    let total: f32 = amounts.iter().sum();
+    assert!((total - 21.0).abs() < 1e-3, "total coverage {}", total);
+    assert!(amounts.iter().filter(|&&a| a > 0.05 && a < 0.95).count() >= 10);
+
+    let mut frame = Frame::new(30, 10);
+    coverage.resolve(&mut frame);
+    let mut chars: Vec<char> = (0..10).flat_map(|y| frame.row(y).to_vec()).filter(|&c| c != ' ').collect();
+    chars.sort();
+    chars.dedup();
+    assert!(chars.len() >= 4, "only {:?}", chars);
+}
+
+#[test]
+fn quad_fills_exactly_the_cells_inside_it() {
+    let mut frame = Frame::new(12, 8);
+    // A rectangle split along its diagonal, from the middle of cell (2, 1) to the middle of cell (9, 5)
+    let [top_left, top_right, bottom_right, bottom_left] = [[2.0, 1.0, 0.5], [9.0, 1.0, 0.5], [9.0, 5.0, 0.5], [2.0, 5.0, 0.5]];
+    fill_triangle(&mut frame, top_left, top_right, bottom_right, '#', None);
+    fill_triangle(&mut frame, top_left, bottom_right, bottom_left, '#', None);
+    for y in 0..frame.height() {
+        for x in 0..frame.width() {
+            let inside = (2..=9).contains(&x) && (1..=5).contains(&y);
+            assert_eq!(frame.row(y)[x], if inside { '#' } else { ' ' }, "at ({}, {})", x, y);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Tests of recording the animation and playing it back
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use simple_rust_cube::quat::{tumble, Quat};
+use simple_rust_cube::record::{parse_recording, FrameState, RecordingError};
+
+/// Runs the binary on a small screen with the given arguments and returns what it wrote to stdout
+fn run(args: &[&str]) -> Vec<u8> {
+    let output = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube")).args(["--width", "40", "--height", "16"]).args(args).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    output.stdout
+}
+
+/// A file in the temporary directory for this test alone, which does not exist yet
+fn temp_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("simple-rust-cube-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn frame_state_survives_a_round_trip_exactly() {
+    let state = FrameState { orientation: tumble(1.7, [0.3, 0.33, 0.1]), fov: 72.5, distance: 3.25 };
+    let line = state.to_string();
+    assert_eq!(line.parse::<FrameState>(), Ok(state));
+    assert_eq!(parse_recording(&format!("{}\n\n{}\n", line, line)), Ok(vec![state, state]));
+}
+
+#[test]
+fn bad_lines_are_reported() {
+    assert_eq!(parse_recording("0 0 0 1 90 3\n0 0 0 1 90\n").unwrap_err().line, 2);
+    let RecordingError { line, message } = parse_recording("0 0 0 one 90 3").unwrap_err();
+    assert_eq!(line, 1);
+    assert!(message.contains("'one'"));
+    assert_eq!(parse_recording("0 0 0 1 90 3").unwrap()[0].orientation, Quat::IDENTITY);
+}
+
+#[test]
+fn playback_draws_the_recorded_frames() {
+    let path = temp_file("recording.txt");
+    let path_arg = path.to_str().unwrap();
+    let recorded = run(&["--frames", "12", "--fps", "0", "--rx", "0.4", "--speed", "8", "--distance", "5", "--record", path_arg]);
+    let recording = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(recording.lines().count(), 12);
+    // Played back without a number of frames, the recording ends the animation, and the motion given on the command line is ignored. The camera is put back where it was recorded, here farther away than usual.
+    let played = run(&["--playback", path_arg, "--ry", "2"]);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(String::from_utf8(played).unwrap(), String::from_utf8(recorded).unwrap());
+}
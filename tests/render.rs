@@ -0,0 +1,145 @@
+//! Tests of rendering whole frames through the public API
+
+use simple_rust_cube::{back_to_front, frame_to_string, project, projection_matrix, render_frame, render_scene, rotation_y, screen_cell, translation, viewport, Charset, Frame, Mesh, Model, RenderMode, RenderOptions, Vector};
+
+/// The cells of a frame that something has been drawn into
+fn drawn_cells(frame: &Frame) -> Vec<(usize, usize)> {
+    (0..frame.height()).flat_map(|y| (0..frame.width()).map(move |x| (x, y))).filter(|&(x, y)| frame.row(y)[x] != ' ').collect()
+}
+
+#[test]
+fn render_frame_draws_the_cube_at_the_given_size() {
+    let mesh = Mesh::cube();
+    let model_to_world = translation(0.0, 0.0, -4.0) * rotation_y(0.5);
+    let frame = render_frame(&mesh, &model_to_world, &RenderOptions::default(), 50, 24);
+    assert_eq!((frame.width(), frame.height()), (50, 24));
+    assert!(drawn_cells(&frame).len() > 20);
+    // It is the same as a scene of that one model.
+    let scene = render_scene(&[Model { mesh: &mesh, model_to_world }], &RenderOptions::default(), 50, 24);
+    for y in 0..frame.height() {
+        assert_eq!(frame.row(y), scene.row(y));
+    }
+}
+
+#[test]
+fn cube_is_centered_at_any_size() {
+    let mesh = Mesh::cube();
+    // Turned by 45 degrees, the cube looks the same from either side.
+    let model_to_world = translation(0.0, 0.0, -4.0) * rotation_y(std::f32::consts::FRAC_PI_4);
+    for (width, height) in [(120, 50), (40, 20)] {
+        let cells = drawn_cells(&render_frame(&mesh, &model_to_world, &RenderOptions::default(), width, height));
+        let (min_x, max_x) = (cells.iter().map(|c| c.0).min().unwrap(), cells.iter().map(|c| c.0).max().unwrap());
+        let (min_y, max_y) = (cells.iter().map(|c| c.1).min().unwrap(), cells.iter().map(|c| c.1).max().unwrap());
+        // The cube is as far from the left edge as from the right, and from the top as from the bottom.
+        assert!((min_x + max_x).abs_diff(width - 1) <= 1, "{}x{}: columns {} to {}", width, height, min_x, max_x);
+        assert!((min_y + max_y).abs_diff(height - 1) <= 1, "{}x{}: rows {} to {}", width, height, min_y, max_y);
+    }
+}
+
+#[test]
+fn faces_are_ordered_from_the_farthest_to_the_nearest() {
+    let mesh = Mesh::cube();
+    let near = Model { mesh: &mesh, model_to_world: translation(0.5, 0.0, -4.0) };
+    let far = Model { mesh: &mesh, model_to_world: translation(-0.5, 0.0, -9.0) };
+    let models = [near, far];
+    let view_pos: Vec<Vec<Vector>> = models.iter().map(|model| mesh.vertices.iter().map(|v| model.model_to_world * *v).collect()).collect();
+    let order = back_to_front(&models, &view_pos);
+    assert_eq!(order.len(), 2 * mesh.faces.len());
+    // All of the far cube comes before any of the near one, and within each cube the back face comes first and the front face last.
+    assert!(order[..6].iter().all(|&(model, _)| model == 1) && order[6..].iter().all(|&(model, _)| model == 0));
+    let face_z = |(model, face): (usize, usize)| mesh.faces[face].iter().map(|&i| view_pos[model][i].0[2]).sum::<f32>() / 4.0;
+    assert!(order.windows(2).all(|pair| face_z(pair[0]) <= face_z(pair[1])));
+    assert!((face_z(order[0]) + 10.0).abs() < 1e-6);
+    assert!((face_z(order[11]) + 3.0).abs() < 1e-6);
+}
+
+#[test]
+fn two_cubes_side_by_side_both_appear() {
+    let mesh = Mesh::cube();
+    let models = [-2.5, 2.5].map(|x| Model { mesh: &mesh, model_to_world: translation(x, 0.0, -8.0) * rotation_y(0.5) });
+    let (width, height) = (80, 24);
+    let cells = drawn_cells(&render_scene(&models, &RenderOptions::default(), width, height));
+    // Each cube is drawn in its own half of the screen, with nothing in the middle between them.
+    assert!(cells.iter().filter(|&&(x, _)| x < width / 2).count() > 20);
+    assert!(cells.iter().filter(|&&(x, _)| x >= width / 2).count() > 20);
+    assert!(cells.iter().all(|&(x, _)| x.abs_diff(width / 2) > 2));
+}
+
+/// The cell a character has been drawn into, which must be the only one it is in
+fn cell_of(frame: &Frame, glyph: char) -> (usize, usize) {
+    let cells: Vec<(usize, usize)> = drawn_cells(frame).into_iter().filter(|&(x, y)| frame.row(y)[x] == glyph).collect();
+    assert_eq!(cells.len(), 1, "{:?} is in {:?}", glyph, cells);
+    cells[0]
+}
+
+#[test]
+fn axes_point_along_x_y_and_z_without_rotation() {
+    let nothing = Mesh { vertices: Vec::new(), faces: Vec::new() };
+    // The axes are not turned at all. The origin is up and to the right of the middle of the screen, so that the z-axis, coming straight at the camera, points away from the middle.
+    let model_to_world = translation(1.0, 0.5, -4.0);
+    let options = RenderOptions { axes: true, ..RenderOptions::default() };
+    let (width, height) = (80, 40);
+    let frame = render_frame(&nothing, &model_to_world, &options, width, height);
+    let [x, y, _] = viewport(&project(&projection_matrix(&options, width, height), &(model_to_world * Vector([0.0, 0.0, 0.0, 1.0]))).unwrap(), width, height);
+    let origin = (x.round() as isize, y.round() as isize);
+    let from_origin = |glyph: char| {
+        let (x, y) = cell_of(&frame, glyph);
+        (x as isize - origin.0, y as isize - origin.1)
+    };
+    // The x-axis points right, the y-axis up and the z-axis further up and to the right.
+    let (x_axis, y_axis, z_axis) = (from_origin('x'), from_origin('y'), from_origin('z'));
+    assert!(x_axis.0 > 0 && x_axis.1 == 0, "{:?}", x_axis);
+    assert!(y_axis.0 == 0 && y_axis.1 < 0, "{:?}", y_axis);
+    assert!(z_axis.0 > 0 && z_axis.1 < 0, "{:?}", z_axis);
+}
+
+#[test]
+fn vertex_zero_is_marked_where_it_is_projected() {
+    // Turned half way round, vertex 0 is the bottom right corner of the face towards the camera.
+    let model_to_world = translation(0.0, 0.0, -4.0) * rotation_y(std::f32::consts::PI + 0.3);
+    let (width, height) = (60, 30);
+    let options = RenderOptions { vertex_markers: true, vertex_labels: true, ..RenderOptions::default() };
+    let mesh = Mesh::cube();
+    let frame = render_frame(&mesh, &model_to_world, &options, width, height);
+    let [x, y, _] = viewport(&project(&projection_matrix(&options, width, height), &(model_to_world * mesh.vertices[0])).unwrap(), width, height);
+    let [x, y] = screen_cell(x, y).unwrap();
+    assert_eq!(frame.row(y)[x], options.charset.vertex);
+    // Its label is written just to the right of it.
+    assert_eq!(frame.row(y)[x + 1], '0');
+}
+
+#[test]
+fn vertices_beyond_the_far_plane_are_not_marked() {
+    // The far plane cuts through the middle of the cube, so only the vertices of the face towards the camera are in front of it.
+    let model_to_world = translation(0.0, 0.0, -4.0) * rotation_y(0.3);
+    let (width, height) = (60, 30);
+    let options = RenderOptions { vertex_markers: true, far: 4.0, ..RenderOptions::default() };
+    let mesh = Mesh::cube();
+    let frame = render_frame(&mesh, &model_to_world, &options, width, height);
+    let projection = projection_matrix(&options, width, height);
+    let mut marked = 0;
+    for &vertex in &mesh.vertices {
+        let view = model_to_world * vertex;
+        let [x, y, _] = viewport(&project(&projection, &view).unwrap(), width, height);
+        let [x, y] = screen_cell(x, y).unwrap();
+        let in_front = -view.0[2] < options.far;
+        assert_eq!(frame.row(y)[x] == options.charset.vertex, in_front, "{:?}", vertex);
+        marked += in_front as usize;
+    }
+    assert_eq!(marked, 4);
+}
+
+#[test]
+fn custom_charset_draws_with_its_own_characters() {
+    let charset: Charset = "=!><o@".parse().unwrap();
+    assert_eq!(charset, Charset { horizontal: '=', vertical: '!', rising: '>', falling: '<', vertex: 'o', fill: '@' });
+    let model_to_world = translation(0.0, 0.0, -4.0) * rotation_y(0.5);
+    let wireframe = RenderOptions { charset, vertex_markers: true, ..RenderOptions::default() };
+    let filled = RenderOptions { charset, mode: RenderMode::Filled, ..RenderOptions::default() };
+    for (options, expected) in [(wireframe, "=!><o"), (filled, "@")] {
+        let text = frame_to_string(&render_frame(&Mesh::cube(), &model_to_world, &options, 60, 24));
+        // The frame holds the characters of the charset and nothing else but spaces and newlines.
+        assert!(text.chars().all(|c| c == ' ' || c == '\n' || expected.contains(c)), "{}", text);
+        assert!(text.contains(expected.chars().next().unwrap()));
+    }
+}
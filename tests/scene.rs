@@ -0,0 +1,62 @@
+//! Tests of reading scene files, only built with the scene feature
+#![cfg(feature = "scene")]
+
+use simple_rust_cube::scene::SceneConfig;
+use simple_rust_cube::{approx_eq, look_at, matrix_approx_eq, Light, RenderMode, RenderOptions, Vector};
+
+const SAMPLE : &str = r#"
+shape = "tetrahedron"
+light = [0.0, 1.0, 1.0]
+axis = [1.0, 0.0, 0.0]
+speed = 0.5
+mode = "shaded"
+
+[camera]
+position = [0.0, 2.0, 4.0]
+target = [0.0, 0.5, 0.0]
+"#;
+
+#[test]
+fn sample_scene_sets_up_the_renderer() {
+    let scene: SceneConfig = SAMPLE.parse().unwrap();
+    assert_eq!(scene.shape.as_deref(), Some("tetrahedron"));
+    assert_eq!(scene.model, None);
+    assert_eq!(scene.speed, Some(0.5));
+    assert_eq!(scene.mode, Some(RenderMode::Shaded));
+    assert!(approx_eq(&scene.spin_axis(), &Vector([1.0, 0.0, 0.0, 0.0]), 0.0));
+    // The camera's up direction was left out, so it is the default one.
+    assert_eq!(scene.camera.up, [0.0, 1.0, 0.0]);
+
+    let options = scene.render_options();
+    let view = look_at(&Vector([0.0, 2.0, 4.0, 1.0]), &Vector([0.0, 0.5, 0.0, 1.0]), &Vector([0.0, 1.0, 0.0, 0.0]));
+    assert!(matrix_approx_eq(&options.view, &view, 1e-6));
+    assert_eq!(options.mode, RenderMode::Shaded);
+    assert_eq!(options.lights, vec![Light::new(Vector([0.0, 1.0, 1.0, 0.0]))]);
+}
+
+#[test]
+fn empty_scene_keeps_the_defaults() {
+    let scene: SceneConfig = "".parse().unwrap();
+    assert_eq!(scene, SceneConfig::default());
+    let options = scene.render_options();
+    let defaults = RenderOptions::default();
+    assert_eq!(options.mode, RenderMode::Wireframe);
+    assert!(options.lights.is_empty());
+    assert_eq!(options.fov_y, defaults.fov_y);
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+    assert!("colour = 3".parse::<SceneConfig>().is_err());
+    assert!("mode = \"sketchy\"".parse::<SceneConfig>().is_err());
+}
+
+#[test]
+fn dolly_moves_the_camera_along_the_line_to_its_target() {
+    let scene: SceneConfig = SAMPLE.parse().unwrap();
+    let camera = scene.camera.dolly(2.0 * scene.camera.distance());
+    assert!((camera.distance() - 2.0 * scene.camera.distance()).abs() < 1e-5);
+    assert_eq!(camera.target, scene.camera.target);
+    let [x, y, z] = camera.position;
+    assert!(approx_eq(&Vector([x, y, z, 1.0]), &Vector([0.0, 3.5, 8.0, 1.0]), 1e-5));
+}
@@ -0,0 +1,42 @@
+//! Tests of scaling shapes so that they stay on the screen however they turn
+
+use simple_rust_cube::{look_at, project, projection_matrix, rotation_x, rotation_y, scale, screen_fit_scale, viewport, Mesh, Projection, RenderOptions, Vector, FIT_RADIUS};
+
+/// Checks that every vertex of the mesh, scaled by screen_fit_scale and turned through a sweep of angles around two axes, lands on a screen of width by height cells
+fn assert_stays_on_screen(mesh: &Mesh, options: &RenderOptions, width: usize, height: usize) {
+    let size = screen_fit_scale(mesh.radius(), 2.5, options, width, height);
+    let projection = projection_matrix(options, width, height);
+    for yaw in 0..36 {
+        for pitch in 0..36 {
+            let model_to_view = options.view * scale(size, size, size) * rotation_x(pitch as f32 * 0.175) * rotation_y(yaw as f32 * 0.175);
+            for vertex in &mesh.vertices {
+                let [x, y, _] = viewport(&project(&projection, &(model_to_view * *vertex)).unwrap(), width, height);
+                assert!((0.0..width as f32).contains(&x) && (0.0..height as f32).contains(&y), "vertex at {}, {} after yaw {} and pitch {}", x, y, yaw, pitch);
+            }
+        }
+    }
+}
+
+/// Options for a camera 2.5 from the origin along the z-axis, looking at it
+fn options(projection: Projection) -> RenderOptions {
+    RenderOptions {
+        projection,
+        view: look_at(&Vector([0.0, 0.0, 2.5, 1.0]), &Vector([0.0, 0.0, 0.0, 1.0]), &Vector([0.0, 1.0, 0.0, 0.0])),
+        ..RenderOptions::default()
+    }
+}
+
+#[test]
+fn cube_stays_on_the_screen_however_it_turns() {
+    for (width, height) in [(80, 40), (40, 16), (20, 40)] {
+        assert_stays_on_screen(&Mesh::cube(), &options(Projection::Perspective), width, height);
+        assert_stays_on_screen(&Mesh::cube(), &options(Projection::Orthographic), width, height);
+    }
+}
+
+#[test]
+fn narrow_view_shrinks_the_cube_more() {
+    let narrow = RenderOptions { fov_y: 0.5, ..options(Projection::Perspective) };
+    let wide = options(Projection::Perspective);
+    assert!(screen_fit_scale(FIT_RADIUS, 2.5, &narrow, 80, 40) < screen_fit_scale(FIT_RADIUS, 2.5, &wide, 80, 40));
+}
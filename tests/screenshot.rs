@@ -0,0 +1,37 @@
+//! Tests of saving screenshots of frames as text
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use simple_rust_cube::export::{save_screenshot, screenshot_name};
+use simple_rust_cube::{frame_to_string, Frame};
+
+/// The time a number of milliseconds after the Unix epoch
+fn at(millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis)
+}
+
+#[test]
+fn name_is_the_time_in_utc() {
+    assert_eq!(screenshot_name(at(0)), "screenshot-19700101-000000.000.txt");
+    assert_eq!(screenshot_name(at(1_706_745_599_123)), "screenshot-20240131-235959.123.txt");
+}
+
+#[test]
+fn name_counts_leap_days() {
+    assert_eq!(screenshot_name(at(951_782_400_000)), "screenshot-20000229-000000.000.txt");
+    assert_eq!(screenshot_name(at(951_868_800_000)), "screenshot-20000301-000000.000.txt");
+}
+
+#[test]
+fn screenshot_holds_the_frame_as_text() {
+    let dir = std::env::temp_dir().join(format!("simple-rust-cube-{}-screenshot", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut frame = Frame::new(6, 2);
+    frame.clear();
+    frame.write_text(1, 1, "cube");
+    let time = at(1_706_745_599_123);
+    let path = save_screenshot(&dir, &frame, time).unwrap();
+    assert_eq!(path, dir.join(screenshot_name(time)));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), frame_to_string(&frame));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
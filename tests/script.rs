@@ -0,0 +1,49 @@
+//! Pipes scripts to the binary with --script and checks the frames it writes.
+
+mod common;
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use common::assert_golden;
+
+/// Runs the binary with --script on a small screen, feeding it the script on stdin
+fn run_script(script: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube"))
+        .args(["--script", "--width", "40", "--height", "16"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(script.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn script_frames_match_golden() {
+    let output = run_script("# turn a little, then zoom in\nyaw 0.4\npitch -0.3\nrender\n\nzoom 1.5\nyaw 0.2\nrender\n");
+    assert!(output.status.success());
+    assert_golden("script.txt", &String::from_utf8(output.stdout).unwrap());
+}
+
+#[test]
+fn script_writes_a_frame_per_render() {
+    let output = run_script("render\nyaw 0.1\nrender\nrender\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 3 * 16);
+}
+
+#[test]
+fn script_without_render_writes_nothing() {
+    let output = run_script("yaw 0.1\nzoom 2\n");
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn unknown_command_is_an_error() {
+    let output = run_script("render\nroll 1\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("line 2"));
+}
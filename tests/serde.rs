@@ -0,0 +1,21 @@
+//! Tests of saving vectors and matrices as JSON, only built with the serde feature
+#![cfg(feature = "serde")]
+
+use simple_rust_cube::{approx_eq, look_at, matrix_approx_eq, Matrix, Vector};
+
+#[test]
+fn vector_round_trips_through_json() {
+    let v = Vector([0.1, -2.5, 3.0, 1.0]);
+    let json = serde_json::to_string(&v).unwrap();
+    assert_eq!(json, "[0.1,-2.5,3.0,1.0]");
+    let back: Vector = serde_json::from_str(&json).unwrap();
+    assert!(approx_eq(&back, &v, 1e-6));
+}
+
+#[test]
+fn matrix_round_trips_through_json() {
+    let m = look_at(&Vector([1.0, 2.0, 2.5, 1.0]), &Vector([0.0, 0.0, 0.0, 1.0]), &Vector([0.0, 1.0, 0.0, 0.0]));
+    let json = serde_json::to_string(&m).unwrap();
+    let back: Matrix = serde_json::from_str(&json).unwrap();
+    assert!(matrix_approx_eq(&back, &m, 1e-6));
+}
@@ -0,0 +1,56 @@
+//! Tests of the ground and the shadows cast onto it
+
+use simple_rust_cube::{look_at, render_frame, rotation_x, rotation_y, shadow_matrix, Frame, Light, Matrix, Mesh, Projection, RenderMode, RenderOptions, Vector, GROUND, GROUND_HEIGHT, SHADOW};
+
+/// The cube turned so that its shadow is not just a square
+fn placement() -> Matrix {
+    rotation_y(0.5) * rotation_x(0.3)
+}
+
+/// Bounding box of the cells of a frame that hold one of the given characters, as the lowest and highest column and row
+fn extent(frame: &Frame, glyphs: &[char]) -> [usize; 4] {
+    let cells: Vec<(usize, usize)> = (0..frame.height()).flat_map(|y| (0..frame.width()).map(move |x| (x, y))).filter(|&(x, y)| glyphs.contains(&frame.row(y)[x])).collect();
+    assert!(!cells.is_empty());
+    let xs = cells.iter().map(|c| c.0);
+    let ys = cells.iter().map(|c| c.1);
+    [xs.clone().min().unwrap(), xs.max().unwrap(), ys.clone().min().unwrap(), ys.max().unwrap()]
+}
+
+#[test]
+fn shadow_matrix_flattens_points_onto_the_ground_along_the_light() {
+    let shadow = shadow_matrix(&Vector([1.0, 2.0, -1.0, 0.0]), -1.0);
+    let [x, y, z, w] = (shadow * Vector([0.0, 1.0, 0.0, 1.0])).0;
+    // The point is two units above the ground, so its shadow falls one unit away from the light in x and z.
+    assert_eq!([x, y, z, w], [-1.0, -1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn straight_down_light_keeps_x_and_z() {
+    let shadow = shadow_matrix(&Vector([0.0, 1.0, 0.0, 0.0]), GROUND_HEIGHT);
+    for vertex in Mesh::cube().vertices {
+        let [x, y, z, _] = (shadow * (placement() * vertex)).0;
+        let [vx, _, vz, _] = (placement() * vertex).0;
+        assert_eq!([x, y, z], [vx, GROUND_HEIGHT, vz]);
+    }
+}
+
+#[test]
+fn shadow_of_straight_down_light_covers_the_cube_seen_from_above() {
+    // Seen from straight above without perspective, the shadow cast straight down lies exactly under the cube.
+    let view = look_at(&Vector([0.0, 5.0, 0.0, 1.0]), &Vector([0.0, 0.0, 0.0, 1.0]), &Vector([0.0, 0.0, -1.0, 0.0]));
+    let options = RenderOptions {
+        projection: Projection::Orthographic,
+        view,
+        lights: vec![Light::new(Vector([0.0, 1.0, 0.0, 0.0]))],
+        ..RenderOptions::default()
+    };
+    // Only the vertices of the cube are drawn, so that it does not hide its shadow.
+    let shadow = render_frame(&Mesh::cube(), &placement(), &RenderOptions { mode: RenderMode::Points, shadow: true, ..options.clone() }, 60, 30);
+    let cube = render_frame(&Mesh::cube(), &placement(), &RenderOptions { mode: RenderMode::Filled, ..options }, 60, 30);
+    let [shadow_left, shadow_right, shadow_top, shadow_bottom] = extent(&shadow, &[SHADOW]);
+    let [cube_left, cube_right, cube_top, cube_bottom] = extent(&cube, &['#']);
+    assert!(shadow_left.abs_diff(cube_left) <= 1 && shadow_right.abs_diff(cube_right) <= 1);
+    assert!(shadow_top.abs_diff(cube_top) <= 1 && shadow_bottom.abs_diff(cube_bottom) <= 1);
+    // The ground shows around the shadow.
+    assert!(extent(&shadow, &[GROUND])[0] < shadow_left);
+}
@@ -0,0 +1,41 @@
+//! Renders fixed frames of the cube and compares them with the golden text files in tests/golden, so that any change to the output is noticed. Run with UPDATE_GOLDEN=1 to write the current output as the new golden files instead.
+
+mod common;
+
+use common::assert_golden;
+use simple_rust_cube::{frame_to_string, look_at, render_frame, rotation_x, rotation_y, Mesh, RenderOptions, Vector, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+/// Renders the cube as it is turned at time t, seen from the same place as in the animation
+fn render_cube(t: f32) -> String {
+    let options = RenderOptions {
+        view: look_at(&Vector([0.0, 0.0, 2.5, 1.0]), &Vector([0.0, 0.0, 0.0, 1.0]), &Vector([0.0, 1.0, 0.0, 0.0])),
+        ..RenderOptions::default()
+    };
+    let model_to_world = rotation_x(0.5 * t) * rotation_y(t);
+    frame_to_string(&render_frame(&Mesh::cube(), &model_to_world, &options, SCREEN_WIDTH, SCREEN_HEIGHT))
+}
+
+#[test]
+fn cube_at_0_0() {
+    assert_golden("cube_t0.0.txt", &render_cube(0.0));
+}
+
+#[test]
+fn cube_at_0_5() {
+    assert_golden("cube_t0.5.txt", &render_cube(0.5));
+}
+
+#[test]
+fn cube_at_1_0() {
+    assert_golden("cube_t1.0.txt", &render_cube(1.0));
+}
+
+#[test]
+fn ascii_output_is_the_same_as_from_the_byte_buffer() {
+    // This file was written before frames held chars, when each cell was a single byte, so unlike the golden files above it is never updated.
+    let from_bytes: &[u8] = include_bytes!("golden/byte_buffer_t0.7.txt");
+    let text = render_cube(0.7);
+    assert_eq!(text.as_bytes(), from_bytes);
+    // Each cell is still written as one byte.
+    assert!(text.lines().all(|line| line.len() == SCREEN_WIDTH && line.is_ascii()));
+}
@@ -0,0 +1,69 @@
+//! Tests of the render time statistics written with --stats
+
+use std::process::Command;
+use std::time::Duration;
+
+use simple_rust_cube::stats::{RenderStats, StatsSummary};
+
+/// Collects the given render times in milliseconds, in the order given
+fn stats_of(millis: impl IntoIterator<Item = u64>) -> RenderStats {
+    let mut stats = RenderStats::new();
+    for ms in millis {
+        stats.add(Duration::from_millis(ms));
+    }
+    stats
+}
+
+#[test]
+fn no_frames_have_no_summary() {
+    assert_eq!(RenderStats::new().summary(), None);
+}
+
+#[test]
+fn summary_of_one_to_a_hundred_milliseconds() {
+    // Added out of order, so that the summary has to sort them.
+    let stats = stats_of((1..=100).rev());
+    assert_eq!(stats.summary(), Some(StatsSummary {
+        frames: 100,
+        min: Duration::from_millis(1),
+        max: Duration::from_millis(100),
+        mean: Duration::from_micros(50_500),
+        median: Duration::from_millis(50),
+        p90: Duration::from_millis(90),
+        p99: Duration::from_millis(99),
+    }));
+}
+
+#[test]
+fn percentiles_of_few_frames_are_frames_that_were_rendered() {
+    let summary = stats_of([4, 1, 3]).summary().unwrap();
+    assert_eq!(summary.frames, 3);
+    assert_eq!(summary.mean, Duration::from_nanos(2_666_666));
+    assert_eq!(summary.median, Duration::from_millis(3));
+    assert_eq!(summary.p90, Duration::from_millis(4));
+    assert_eq!(summary.p99, Duration::from_millis(4));
+}
+
+#[test]
+fn summary_is_written_in_milliseconds() {
+    let summary = stats_of([2, 1]).summary().unwrap();
+    assert_eq!(summary.to_string(), "render time of 2 frames:\n  \
+        min    1.000 ms\n  \
+        mean   1.500 ms\n  \
+        median 1.000 ms\n  \
+        p90    2.000 ms\n  \
+        p99    2.000 ms\n  \
+        max    2.000 ms\n");
+}
+
+#[test]
+fn stats_are_written_to_stderr_after_the_run() {
+    let output = Command::new(env!("CARGO_BIN_EXE_simple-rust-cube"))
+        .args(["--width", "30", "--height", "10", "--frames", "3", "--fps", "1000", "--stats"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("render time of 3 frames:\n"), "{}", stderr);
+    assert!(!String::from_utf8(output.stdout).unwrap().contains("render time"));
+}
@@ -0,0 +1,167 @@
+//! Tests of fitting the screen to the terminal, of the size it has when the terminal size is unknown, and of how frames are written when stdout is not a terminal
+
+use simple_rust_cube::term::{cursor_up, initial_screen, screen_for_terminal, AlternateScreen, HiddenCursor, Output, ENTER_ALTERNATE_SCREEN, HIDE_CURSOR, LEAVE_ALTERNATE_SCREEN, SHOW_CURSOR};
+use simple_rust_cube::{draw_line, render_frame, rotation_y, translation, Charset, Frame, Glyphs, Mesh, RenderOptions, FACE_COLORS, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+#[test]
+fn screen_leaves_the_last_row_of_the_terminal_free() {
+    assert_eq!(screen_for_terminal([80, 24]), [80, 23]);
+    assert_eq!(screen_for_terminal([132, 50]), [132, 49]);
+}
+
+#[test]
+fn screen_is_at_least_one_character() {
+    assert_eq!(screen_for_terminal([0, 0]), [1, 1]);
+    assert_eq!(screen_for_terminal([10, 1]), [10, 1]);
+}
+
+#[test]
+fn initial_screen_fills_the_terminal() {
+    assert_eq!(initial_screen(Ok([100, 30])), [100, 29]);
+}
+
+#[test]
+fn initial_screen_falls_back_to_the_default_size() {
+    let error = std::io::Error::other("not a terminal");
+    assert_eq!(initial_screen(Err(error)), [SCREEN_WIDTH, SCREEN_HEIGHT]);
+}
+
+#[test]
+fn output_is_plain_unless_stdout_is_a_terminal() {
+    assert_eq!(Output::choose(false, || true), Output::Terminal);
+    assert_eq!(Output::choose(false, || false), Output::Plain);
+    assert_eq!(Output::choose(true, || true), Output::Plain);
+}
+
+/// Composes the second of two frames of a colored line, as it would be written to a terminal or not
+fn compose_second_frame(is_terminal: bool) -> String {
+    let mut frame = Frame::new(8, 3);
+    draw_line(&mut frame, [1.0, 1.0, 0.0], [6.0, 1.0, 0.0], &Charset::default(), Some(196));
+    let mut out = Vec::new();
+    Output::choose(false, || is_terminal).compose_frame(&frame, Glyphs::Ascii, false, &mut out);
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn frames_for_a_terminal_are_drawn_over_each_other_in_color() {
+    let out = compose_second_frame(true);
+    assert!(out.starts_with("\x1b[3A\x1b[2K"), "{:?}", out);
+    assert!(out.contains("\x1b[38;5;196m------\x1b[0m"), "{:?}", out);
+}
+
+#[test]
+fn frames_for_a_pipe_are_plain_text_apart_from_each_other() {
+    assert_eq!(compose_second_frame(false), "\n        \n ------ \n        \n");
+}
+
+#[test]
+fn hidden_cursor_is_shown_again_when_the_guard_is_dropped() {
+    let mut out = Vec::new();
+    let cursor = HiddenCursor::with_writer(&mut out);
+    drop(cursor);
+    assert_eq!(String::from_utf8(out).unwrap(), format!("{}{}", HIDE_CURSOR, SHOW_CURSOR));
+}
+
+#[test]
+fn main_screen_is_restored_when_the_guard_is_dropped() {
+    let mut out = Vec::new();
+    let screen = AlternateScreen::with_writer(&mut out);
+    drop(screen);
+    assert_eq!(String::from_utf8(out).unwrap(), format!("{}{}", ENTER_ALTERNATE_SCREEN, LEAVE_ALTERNATE_SCREEN));
+}
+
+#[test]
+fn cursor_up_is_a_single_escape_sequence() {
+    assert_eq!(cursor_up(40), "\x1b[40A");
+    assert!(!cursor_up(7).contains(';'));
+}
+
+/// What a terminal of the given number of columns and rows shows after the bytes are written to it from its top left corner, going by only the escape sequences frames are composed of
+fn screen_after(bytes: &[u8], [columns, rows]: [usize; 2]) -> Vec<String> {
+    let mut screen = vec![vec![' '; columns]; rows];
+    let [mut column, mut row] = [0, 0];
+    let mut chars = std::str::from_utf8(bytes).unwrap().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => {
+                assert_eq!(chars.next(), Some('['));
+                let mut params = String::new();
+                let command = loop {
+                    match chars.next().unwrap() {
+                        c @ ('0'..='9' | ';') => params.push(c),
+                        c => break c,
+                    }
+                };
+                match command {
+                    'A' => row -= params.parse::<usize>().unwrap(),
+                    'K' => screen[row].fill(' '),
+                    'm' => {}
+                    _ => panic!("unexpected escape sequence {:?}", command),
+                }
+            }
+            '\n' => [column, row] = [0, row + 1],
+            _ => {
+                screen[row][column] = c;
+                column += 1;
+            }
+        }
+    }
+    screen.iter().map(|row| row.iter().collect()).collect()
+}
+
+#[test]
+fn next_frame_leaves_nothing_of_the_previous_one() {
+    let cube = |angle: f32, width: usize| render_frame(&Mesh::cube(), &(translation(0.0, 0.0, -4.0) * rotation_y(angle)), &RenderOptions::default(), width, 16);
+    let mut out = Vec::new();
+    Output::Terminal.compose_frame(&cube(0.7, 40), Glyphs::Ascii, true, &mut out);
+    // The cube turns from its widest to face on, and then the frame gets narrower than the last one.
+    for next in [cube(0.0, 40), cube(0.0, 24)] {
+        Output::Terminal.compose_frame(&next, Glyphs::Ascii, false, &mut out);
+        let mut alone = Vec::new();
+        Output::Terminal.compose_frame(&next, Glyphs::Ascii, true, &mut alone);
+        assert_eq!(screen_after(&out, [40, 17]), screen_after(&alone, [40, 17]));
+    }
+}
+
+#[test]
+fn frame_with_a_character_beyond_ascii_is_written_whole() {
+    // U+00FF is the character the byte 0xFF stands for in Latin-1, which on its own is not valid UTF-8.
+    let mut frame = Frame::new(4, 2);
+    frame.plot(1, 0, 0.5, '\u{ff}', None);
+    frame.plot(2, 1, 0.5, '\u{ff}', Some(196));
+    for output in [Output::Terminal, Output::Plain] {
+        for first in [true, false] {
+            let mut out = Vec::new();
+            output.compose_frame(&frame, Glyphs::Ascii, first, &mut out);
+            let text = String::from_utf8(out).unwrap();
+            assert_eq!(text.matches('\u{ff}').count(), 2, "{:?}", text);
+        }
+    }
+}
+
+/// The escape sequences in the bytes that move the cursor, such as cursor_up
+fn cursor_moves(bytes: &[u8]) -> Vec<String> {
+    let text = std::str::from_utf8(bytes).unwrap();
+    text.split('\x1b')
+        .skip(1)
+        .map(|rest| {
+            let end = rest.find(|c: char| c.is_ascii_alphabetic()).unwrap();
+            format!("\x1b{}", &rest[..=end])
+        })
+        .filter(|sequence| sequence.ends_with(['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'd', 'f']))
+        .collect()
+}
+
+#[test]
+fn each_frame_moves_the_cursor_once() {
+    let frame = render_frame(&Mesh::cube(), &(translation(0.0, 0.0, -4.0) * rotation_y(0.7)), &RenderOptions { colors: Some(FACE_COLORS), ..RenderOptions::default() }, 40, 16);
+    // The first frame is drawn where the cursor already is.
+    let mut out = Vec::new();
+    Output::Terminal.compose_frame(&frame, Glyphs::Ascii, true, &mut out);
+    assert_eq!(cursor_moves(&out), Vec::<String>::new());
+    for glyphs in [Glyphs::Ascii, Glyphs::HalfBlocks, Glyphs::BoxDrawing, Glyphs::Braille] {
+        let mut out = Vec::new();
+        Output::Terminal.compose_frame(&frame, glyphs, false, &mut out);
+        assert_eq!(cursor_moves(&out), [cursor_up(frame.glyph_rows(glyphs))]);
+    }
+}
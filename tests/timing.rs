@@ -0,0 +1,44 @@
+//! Tests of timing the frames of the animation
+
+use std::time::Duration;
+
+use simple_rust_cube::timing::{angle_at, frame_delay, hud_text};
+use simple_rust_cube::{render_frame, rotation_y, translation, Mesh, RenderOptions};
+
+#[test]
+fn angle_grows_with_time_and_velocity() {
+    assert_eq!(angle_at(Duration::ZERO, 2.0), 0.0);
+    assert!((angle_at(Duration::from_millis(1500), 2.0) - 3.0).abs() < 1e-6);
+    assert!((angle_at(Duration::from_secs(10), 0.33) - 3.3).abs() < 1e-5);
+    // A negative velocity turns the other way, and a velocity of 0 not at all.
+    assert!((angle_at(Duration::from_secs(2), -0.5) + 1.0).abs() < 1e-6);
+    assert_eq!(angle_at(Duration::from_secs(5), 0.0), 0.0);
+}
+
+#[test]
+fn frame_delay_draws_the_frames_per_second_asked_for() {
+    assert_eq!(frame_delay(1), Some(Duration::from_secs(1)));
+    assert_eq!(frame_delay(25), Some(Duration::from_millis(40)));
+    // Otherwise the delay is the length of a frame, rounded down to whole milliseconds.
+    for fps in [30, 60, 144] {
+        let short_by = Duration::from_secs(1).as_secs_f64() / fps as f64 - frame_delay(fps).unwrap().as_secs_f64();
+        assert!((0.0..0.001).contains(&short_by), "{} fps: {} s short", fps, short_by);
+    }
+    // A frame rate of 0 draws the frames as fast as possible.
+    assert_eq!(frame_delay(0), None);
+}
+
+#[test]
+fn hud_shows_the_frame_rate_in_the_top_row() {
+    // 40 ms a frame is 25 frames per second.
+    let text = hud_text(Some(Duration::from_millis(40)), 123);
+    assert_eq!(text, "25.0 fps  40.0 ms  frame 123");
+    let mut frame = render_frame(&Mesh::cube(), &(translation(0.0, 0.0, -2.5) * rotation_y(0.5)), &RenderOptions::default(), 60, 20);
+    frame.write_text(0, 0, &text);
+    let row: String = frame.row(0).iter().collect();
+    assert_eq!(&row[..text.len()], text);
+    // Before a frame has been timed there is no rate to show.
+    assert_eq!(hud_text(None, 0), "-- fps  -- ms  frame 0");
+    // Nor is there when two frames were drawn too quickly for the clock to tell them apart.
+    assert_eq!(hud_text(Some(Duration::ZERO), 7), "-- fps  0.0 ms  frame 7");
+}
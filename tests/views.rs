@@ -0,0 +1,47 @@
+//! Tests of the blueprint views drawn by render_views_into
+
+use simple_rust_cube::{look_at, render_scene, render_views_into, rotation_x, rotation_y, Frame, Mesh, Model, Projection, RenderOptions, Vector};
+
+/// Whether anything was drawn in the part of a frame from column x and row y that is width by height cells
+fn drawn_in(frame: &Frame, [x, y]: [usize; 2], [width, height]: [usize; 2]) -> bool {
+    (y..y + height).any(|row| frame.row(row)[x..x + width].iter().any(|&c| c != ' '))
+}
+
+/// Renders the turned cube as a blueprint, seen from the usual place in the top right quadrant
+fn blueprint() -> Frame {
+    let mesh = Mesh::cube();
+    let model = Model { mesh: &mesh, model_to_world: rotation_x(0.4) * rotation_y(0.7) };
+    let options = RenderOptions {
+        view: look_at(&Vector([0.0, 0.0, 2.5, 1.0]), &Vector([0.0, 0.0, 0.0, 1.0]), &Vector([0.0, 1.0, 0.0, 0.0])),
+        ..RenderOptions::default()
+    };
+    let mut frame = Frame::new(80, 40);
+    render_views_into(&mut frame, &[model], &options);
+    frame
+}
+
+#[test]
+fn every_quadrant_has_a_wireframe() {
+    let frame = blueprint();
+    for corner in [[0, 0], [40, 0], [0, 20], [40, 20]] {
+        assert!(drawn_in(&frame, corner, [40, 20]), "nothing drawn at {:?}", corner);
+        // Each view is drawn only in its own quadrant, within its edges.
+        assert!(frame.row(corner[1]).iter().skip(corner[0]).take(40).all(|&c| c == ' '));
+    }
+}
+
+#[test]
+fn front_view_is_orthographic_from_the_front() {
+    let mesh = Mesh::cube();
+    let model = Model { mesh: &mesh, model_to_world: rotation_x(0.4) * rotation_y(0.7) };
+    let options = RenderOptions {
+        projection: Projection::Orthographic,
+        view: look_at(&Vector([0.0, 0.0, 5.0, 1.0]), &Vector([0.0, 0.0, 0.0, 1.0]), &Vector([0.0, 1.0, 0.0, 0.0])),
+        ..RenderOptions::default()
+    };
+    let front = render_scene(&[model], &options, 40, 20);
+    let frame = blueprint();
+    for y in 0..20 {
+        assert_eq!(&frame.row(20 + y)[..40], front.row(y));
+    }
+}